@@ -12,7 +12,7 @@ fn log_error(message: &dyn Error) {
     eprintln!();
 }
 
-fn main() {
+fn main() -> Result<(), Box<dyn Error>> {
     // Basic usage example of the smart home system
     let mut home = create_home!(
         "My Smart Home",
@@ -51,27 +51,17 @@ fn main() {
     );
 
     // Switch outlet check
-    {
-        let device = home
-            .get_room("Kitchen Room")
-            .and_then(|room| room.get_device("Teapot Outlet"));
-        let outlet = match device {
-            Some(Device::OutletType(outlet)) => outlet,
-            _ => panic!("Expected OutletType"),
-        };
-        assert_eq!(outlet.state(), OutletState::Off);
-        outlet.switch();
-        assert_eq!(outlet.state(), OutletState::On);
-    }
+    assert_eq!(
+        home.switch("Kitchen Room", "Teapot Outlet")?,
+        OutletState::On
+    );
 
     {
-        let device = home
+        let outlet = home
             .get_room("Living Room")
-            .and_then(|room| room.get_device("Lighter"));
-        let outlet = match device {
-            Some(Device::OutletType(outlet)) => outlet,
-            _ => panic!("Expected OutletType"),
-        };
+            .and_then(|room| room.get_device("Lighter"))
+            .ok_or("Lighter is missing")?
+            .try_as_outlet_mut()?;
         assert_eq!(outlet.state(), OutletState::On);
         outlet.turn_off();
         assert_eq!(outlet.state(), OutletState::Off);
@@ -109,4 +99,6 @@ fn main() {
         home.remove_room("New Room");
         assert!(home.get_room("New Room").is_none());
     }
+
+    Ok(())
 }
@@ -0,0 +1,146 @@
+//! `proptest` strategies producing random but valid devices, rooms and homes.
+//!
+//! The strategies only back the property tests of this crate.
+//!
+//! Devices are stored under their own names and rooms under theirs, as the
+//! `create_room!` and `create_home!` macros do. Temperatures are generated in
+//! tenths of a degree so that they survive a trip through decimal text.
+
+use crate::smart_devices::{Celsius, Device, Outlet, OutletState, Thermometer, Watt};
+use crate::smart_home::SmartHome;
+use crate::smart_room::SmartRoom;
+use proptest::collection::hash_map;
+use proptest::prelude::*;
+
+const NAME_PATTERN: &str = "[A-Za-z][A-Za-z0-9 ]{0,15}";
+
+impl Arbitrary for OutletState {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![Just(OutletState::On), Just(OutletState::Off)].boxed()
+    }
+}
+
+impl Arbitrary for Outlet {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            NAME_PATTERN,
+            any::<OutletState>(),
+            0..5_000 as Watt,
+            0..10 as Watt,
+            0..86_400u32,
+            proptest::option::of(0..5_000 as Watt),
+        )
+            .prop_map(
+                |(name, state, power_usage, standby_power, on_seconds, expected_power)| {
+                    let mut outlet =
+                        Outlet::new(name, state, power_usage).with_standby_power(standby_power);
+                    outlet.accumulate_runtime(on_seconds as f64);
+                    match expected_power {
+                        Some(expected_power) => outlet.with_expected_power(expected_power),
+                        None => outlet,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for Thermometer {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (NAME_PATTERN, -400..=600i32, 0..=4usize)
+            .prop_map(|(name, tenths, precision)| {
+                Thermometer::new(name, tenths as Celsius / 10.0).with_precision(precision)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Device {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            4 => any::<Outlet>().prop_map(Device::from),
+            4 => any::<Thermometer>().prop_map(Device::from),
+            1 => Just(Device::Empty),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for SmartRoom {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (NAME_PATTERN, hash_map(NAME_PATTERN, any::<Device>(), 0..6))
+            .prop_map(|(name, mut devices)| {
+                for (key, device) in devices.iter_mut() {
+                    device.set_name(key.clone());
+                }
+                SmartRoom::new(name, devices)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for SmartHome {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            NAME_PATTERN,
+            hash_map(NAME_PATTERN, any::<SmartRoom>(), 0..4),
+        )
+            .prop_map(|(name, mut rooms)| {
+                for (key, room) in rooms.iter_mut() {
+                    room.set_name(key.clone());
+                }
+                SmartHome::new(name, rooms)
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::Information;
+
+    proptest! {
+        #[test]
+        fn smart_home_keys_match_names_test(home in any::<SmartHome>()) {
+            for (room_name, device_name, device) in home.iter_devices() {
+                prop_assert_eq!(home.view_room(room_name).unwrap().name(), room_name);
+                if *device != Device::Empty {
+                    prop_assert_eq!(device.name(), device_name);
+                }
+            }
+        }
+
+        #[test]
+        fn smart_home_power_per_room_sum_test(home in any::<SmartHome>()) {
+            let per_room: Watt = home.power_per_room().iter().map(|(_, power)| power).sum();
+            prop_assert_eq!(per_room, home.total_power_usage());
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn smart_home_json_roundtrip_test(home in any::<SmartHome>()) {
+            let json = serde_json::to_string(&home).unwrap();
+            let decoded: SmartHome = serde_json::from_str(&json).unwrap();
+            prop_assert_eq!(decoded, home);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::smart_home::SmartHome;
+
+const WATT_SECONDS_PER_KWH: f64 = 3_600_000.0;
+
+/// Accumulates the energy consumed by a smart home over simulated time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnergyMeter {
+    watt_seconds: f64,
+}
+
+impl EnergyMeter {
+    /// Creates a new meter with nothing accumulated.
+    pub fn new() -> Self {
+        EnergyMeter::default()
+    }
+
+    /// Advances the simulation by the given time step.
+    ///
+    /// The current power draw of the home is assumed constant for the whole step,
+    /// so outlet state changes are picked up on the next call.
+    ///
+    /// # Arguments
+    ///
+    /// * `home` - The smart home whose current draw is measured.
+    /// * `seconds` - The length of the time step in seconds.
+    pub fn tick(&mut self, home: &SmartHome, seconds: f64) {
+        self.watt_seconds += home.total_power_usage() as f64 * seconds;
+    }
+
+    /// Returns the accumulated energy in watt-seconds.
+    pub fn total_watt_seconds(&self) -> f64 {
+        self.watt_seconds
+    }
+
+    /// Returns the accumulated energy in kilowatt-hours.
+    pub fn total_kwh(&self) -> f64 {
+        self.watt_seconds / WATT_SECONDS_PER_KWH
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_home;
+    use crate::create_room;
+    use crate::smart_devices::{Celsius, Device, OutletDevice, OutletState, Watt};
+    use crate::smart_room::SmartRoom;
+
+    #[test]
+    fn energy_meter_tick_test() {
+        let mut home = create_home!(
+            "My Home",
+            {
+                "Living Room",
+                create_room!(
+                    "Living Room",
+                    "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+                    "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::Off, 100 as Watt),
+                    "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+                )
+            }
+        );
+        let mut meter = EnergyMeter::new();
+        assert_eq!(meter.total_kwh(), 0.0);
+
+        meter.tick(&home, 3600.0);
+        assert_eq!(meter.total_watt_seconds(), 900_000.0);
+        assert_eq!(meter.total_kwh(), 0.25);
+
+        {
+            let device = home
+                .get_room("Living Room")
+                .and_then(|room| room.get_device("Lighter"));
+            let outlet = match device {
+                Some(Device::OutletType(outlet)) => outlet,
+                _ => panic!("Expected OutletType"),
+            };
+            outlet.turn_on();
+        }
+
+        meter.tick(&home, 1800.0);
+        assert_eq!(meter.total_watt_seconds(), 900_000.0 + 350.0 * 1800.0);
+        assert_eq!(meter.total_kwh(), 0.425);
+    }
+}
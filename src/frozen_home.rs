@@ -0,0 +1,158 @@
+//! Read-only snapshot of a smart home optimized for lookups.
+//!
+//! A `FrozenHome` is built once with `SmartHome::freeze` and keeps rooms and
+//! devices in `Vec`s sorted by name, resolved with binary search.
+
+use crate::smart_devices::Device;
+use crate::smart_home::{DeviceAccessError, RoomAccessError};
+use crate::smart_room::{AccessError, SmartRoom};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrozenRoom {
+    name: String,
+    devices: Vec<(String, Device)>,
+}
+
+impl FrozenRoom {
+    pub(crate) fn new(room: SmartRoom) -> Self {
+        let (name, devices) = room.into_parts();
+        FrozenRoom {
+            name,
+            devices: sorted_entries(devices),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the device stored under `key`, or `None` if there is no such device.
+    pub fn view_device(&self, key: &str) -> Option<&Device> {
+        find(&self.devices, key)
+    }
+
+    /// Returns an iterator over `(device_key, device)` pairs sorted by key.
+    pub fn iter_devices(&self) -> impl Iterator<Item = (&str, &Device)> {
+        self.devices
+            .iter()
+            .map(|(key, device)| (key.as_str(), device))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrozenHome {
+    name: String,
+    rooms: Vec<(String, FrozenRoom)>,
+}
+
+impl FrozenHome {
+    pub(crate) fn new(name: String, rooms: HashMap<String, SmartRoom>) -> Self {
+        let rooms = rooms
+            .into_iter()
+            .map(|(key, room)| (key, FrozenRoom::new(room)))
+            .collect();
+        FrozenHome {
+            name,
+            rooms: sorted_entries(rooms),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the room stored under `key`, or `None` if there is no such room.
+    pub fn view_room(&self, key: &str) -> Option<&FrozenRoom> {
+        find(&self.rooms, key)
+    }
+
+    /// Returns a device in a specific room
+    ///
+    /// # Returns
+    ///
+    /// A Result containing either a reference to the device if found, or the same
+    /// DeviceAccessError a `SmartHome` reports for the lookup
+    pub fn device(&self, room_name: &str, device_name: &str) -> Result<&Device, DeviceAccessError> {
+        let room = self
+            .view_room(room_name)
+            .ok_or_else(|| RoomAccessError::room_not_found(room_name, &self.name))?;
+        let device = room
+            .view_device(device_name)
+            .ok_or_else(|| AccessError::device_not_found(device_name, &room.name))?;
+        Ok(device)
+    }
+
+    /// Returns an iterator over the rooms sorted by name.
+    pub fn rooms(&self) -> impl Iterator<Item = &FrozenRoom> {
+        self.rooms.iter().map(|(_, room)| room)
+    }
+}
+
+fn sorted_entries<T>(map: HashMap<String, T>) -> Vec<(String, T)> {
+    let mut entries: Vec<(String, T)> = map.into_iter().collect();
+    entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
+
+fn find<'a, T>(entries: &'a [(String, T)], key: &str) -> Option<&'a T> {
+    entries
+        .binary_search_by(|(entry_key, _)| entry_key.as_str().cmp(key))
+        .ok()
+        .map(|index| &entries[index].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::create_home;
+    use crate::create_room;
+    use crate::smart_devices::{Celsius, Device, OutletState, Watt};
+    use crate::smart_home::SmartHome;
+    use crate::smart_room::SmartRoom;
+
+    #[test]
+    fn frozen_home_matches_live_home_test() {
+        let home = create_home!(
+            "My Smart Home",
+            {
+                "Bedroom",
+                create_room!(
+                    "Bedroom",
+                    "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
+                    "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius)
+                )
+            },
+            {
+                "Living Room",
+                create_room!(
+                    "Living Room",
+                    "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+                    "PC" => Device::new_outlet("PC".to_string(), OutletState::Off, 250 as Watt)
+                )
+            }
+        );
+        let frozen = home.clone().freeze();
+        assert_eq!(frozen.name(), "My Smart Home");
+
+        let lookups = [
+            ("Bedroom", "Attached Outlet"),
+            ("Bedroom", "Electron thermometer"),
+            ("Living Room", "PC"),
+            ("Living Room", "Attached Outlet"),
+            ("Garage", "PC"),
+            ("", ""),
+        ];
+        for (room, device) in lookups {
+            assert_eq!(frozen.device(room, device), home.device(room, device));
+        }
+
+        let room_names: Vec<&str> = frozen.rooms().map(|room| room.name()).collect();
+        assert_eq!(room_names, vec!["Bedroom", "Living Room"]);
+        let frozen_room = frozen.view_room("Living Room").unwrap();
+        assert!(
+            frozen_room
+                .iter_devices()
+                .eq(home.view_room("Living Room").unwrap().iter_devices())
+        );
+    }
+}
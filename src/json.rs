@@ -0,0 +1,62 @@
+//! Minimal JSON writing helpers used by the `to_json_manual` methods.
+//!
+//! These avoid any external dependency, so the JSON export works without the `serde`
+//! feature. Like the rooms and homes using them, they need the `std` feature.
+
+use alloc::format;
+use alloc::string::String;
+
+/// Appends `value` to `out` as a quoted and escaped JSON string.
+pub(crate) fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Appends `value` to `out` as a JSON number, or `null` if it is not finite.
+pub(crate) fn write_f64(out: &mut String, value: f64) {
+    if value.is_finite() {
+        out.push_str(&format!("{:?}", value));
+    } else {
+        out.push_str("null");
+    }
+}
+
+/// Appends `"key":` to `out`.
+pub(crate) fn write_key(out: &mut String, key: &str) {
+    write_string(out, key);
+    out.push(':');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_write_string_escape_test() {
+        let mut out = String::new();
+        write_string(&mut out, "Say \"hi\"\\\n\t\u{1}°C");
+        assert_eq!(out, r#""Say \"hi\"\\\n\t\u0001°C""#);
+    }
+
+    #[test]
+    fn json_write_f64_test() {
+        let mut out = String::new();
+        write_f64(&mut out, 20.0);
+        out.push(',');
+        write_f64(&mut out, 22.5);
+        out.push(',');
+        write_f64(&mut out, f64::NAN);
+        assert_eq!(out, "20.0,22.5,null");
+    }
+}
@@ -1,4 +1,61 @@
+//! Without the default `std` feature only the device model (`smart_devices`,
+//! `registry`, `traits` and `locale`) is built, on top of `core` and `alloc`. Rooms, homes,
+//! the manual JSON export and the helpers built on them need `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(all(test, feature = "std"))]
+mod arbitrary;
+#[cfg(feature = "std")]
+pub mod energy;
+#[cfg(feature = "std")]
+pub mod frozen_home;
+#[cfg(feature = "std")]
+mod json;
+pub mod locale;
+#[cfg(feature = "std")]
+pub mod outlet_group;
+pub mod registry;
 pub mod smart_devices;
+#[cfg(feature = "std")]
 pub mod smart_home;
+#[cfg(feature = "std")]
 pub mod smart_room;
 pub mod traits;
+
+/// Exercises the device model with only `core` and `alloc` available.
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_tests {
+    use crate::smart_devices::{Device, Outlet, OutletDevice, OutletState, Thermometer, Watt};
+    use crate::traits::Information;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn device_model_without_std_test() {
+        let mut outlet = Outlet::new("PC".to_string(), OutletState::Off, 250 as Watt);
+        outlet.switch();
+        assert_eq!(outlet.power_usage(), 250);
+
+        let mut thermometer = Thermometer::new("Bedroom".to_string(), 20.0);
+        thermometer.set_temperature(22.5);
+
+        let devices: Vec<Device> = Vec::from([
+            Device::from(outlet),
+            Device::from(thermometer),
+            Device::Empty,
+        ]);
+        let total: Watt = devices.iter().map(Device::power_usage).sum();
+        assert_eq!(total, 250);
+        assert_eq!(
+            devices[0].info(),
+            "Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"
+        );
+        assert_eq!(
+            devices[1].info(),
+            "Thermometer: Bedroom - Current Temperature: 22.50°C"
+        );
+    }
+}
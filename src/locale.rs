@@ -0,0 +1,86 @@
+/// Languages supported by the localized reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Lang {
+    #[default]
+    En,
+    Ru,
+}
+
+/// Labels used when rendering reports in a particular language.
+///
+/// Room and home labels are only needed with the `std` feature.
+#[derive(Debug)]
+pub(crate) struct Labels {
+    #[cfg(feature = "std")]
+    pub smart_home: &'static str,
+    #[cfg(feature = "std")]
+    pub total_rooms: &'static str,
+    #[cfg(feature = "std")]
+    pub room: &'static str,
+    #[cfg(feature = "std")]
+    pub smart_room: &'static str,
+    #[cfg(feature = "std")]
+    pub total_devices: &'static str,
+    pub smart_outlet: &'static str,
+    pub current_state: &'static str,
+    pub power_usage: &'static str,
+    pub watt: &'static str,
+    pub on: &'static str,
+    pub off: &'static str,
+    pub thermometer: &'static str,
+    pub current_temperature: &'static str,
+    pub no_device: &'static str,
+}
+
+const EN_LABELS: Labels = Labels {
+    #[cfg(feature = "std")]
+    smart_home: "Smart Home",
+    #[cfg(feature = "std")]
+    total_rooms: "Total Rooms",
+    #[cfg(feature = "std")]
+    room: "Room",
+    #[cfg(feature = "std")]
+    smart_room: "Smart Room",
+    #[cfg(feature = "std")]
+    total_devices: "Total devices",
+    smart_outlet: "Smart Outlet",
+    current_state: "Current State",
+    power_usage: "Power Usage",
+    watt: "Watt",
+    on: "On",
+    off: "Off",
+    thermometer: "Thermometer",
+    current_temperature: "Current Temperature",
+    no_device: "No device information available",
+};
+
+const RU_LABELS: Labels = Labels {
+    #[cfg(feature = "std")]
+    smart_home: "Умный дом",
+    #[cfg(feature = "std")]
+    total_rooms: "Всего комнат",
+    #[cfg(feature = "std")]
+    room: "Комната",
+    #[cfg(feature = "std")]
+    smart_room: "Умная комната",
+    #[cfg(feature = "std")]
+    total_devices: "Всего устройств",
+    smart_outlet: "Умная розетка",
+    current_state: "Текущее состояние",
+    power_usage: "Потребляемая мощность",
+    watt: "Вт",
+    on: "Вкл",
+    off: "Выкл",
+    thermometer: "Термометр",
+    current_temperature: "Текущая температура",
+    no_device: "Нет информации об устройстве",
+};
+
+impl Lang {
+    pub(crate) fn labels(self) -> &'static Labels {
+        match self {
+            Lang::En => &EN_LABELS,
+            Lang::Ru => &RU_LABELS,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::smart_devices::{Device, Outlet, OutletDevice};
+use crate::smart_room::SmartRoom;
+use std::collections::BTreeSet;
+
+/// A set of outlets in a room that are switched together.
+///
+/// The group stores device keys rather than references, so it can be applied
+/// to a room at any time without holding a borrow on it. Each key is stored once,
+/// so an outlet is never switched twice by the same call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutletGroup {
+    keys: BTreeSet<String>,
+}
+
+impl OutletGroup {
+    /// Creates a new group from the keys of the outlets to control.
+    ///
+    /// Duplicate keys are dropped.
+    pub fn new(keys: Vec<String>) -> Self {
+        OutletGroup {
+            keys: keys.into_iter().collect(),
+        }
+    }
+
+    /// Returns the keys of the group, sorted.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.keys.iter().map(String::as_str)
+    }
+
+    /// Toggles every outlet of the group in the room.
+    ///
+    /// # Returns
+    ///
+    /// The keys of the group that do not refer to an outlet in the room, sorted.
+    pub fn switch_all(&self, room: &mut SmartRoom) -> Vec<String> {
+        self.apply(room, |outlet| outlet.switch())
+    }
+
+    /// Turns on every outlet of the group in the room.
+    ///
+    /// # Returns
+    ///
+    /// The keys of the group that do not refer to an outlet in the room, sorted.
+    pub fn turn_on_all(&self, room: &mut SmartRoom) -> Vec<String> {
+        self.apply(room, |outlet| outlet.turn_on())
+    }
+
+    /// Turns off every outlet of the group in the room.
+    ///
+    /// # Returns
+    ///
+    /// The keys of the group that do not refer to an outlet in the room, sorted.
+    pub fn turn_off_all(&self, room: &mut SmartRoom) -> Vec<String> {
+        self.apply(room, |outlet| outlet.turn_off())
+    }
+
+    fn apply<F: Fn(&mut Outlet)>(&self, room: &mut SmartRoom, action: F) -> Vec<String> {
+        let mut not_found = Vec::new();
+        for key in &self.keys {
+            match room.get_device(key) {
+                Some(Device::OutletType(outlet)) => action(outlet),
+                _ => not_found.push(key.clone()),
+            }
+        }
+        not_found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_room;
+    use crate::smart_devices::{Celsius, OutletState, Watt};
+    use crate::traits::Information;
+
+    #[test]
+    fn outlet_group_switch_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "Floor lamp" => Device::new_outlet("Floor lamp".to_string(), OutletState::Off, 60 as Watt),
+            "Desk lamp" => Device::new_outlet("Desk lamp".to_string(), OutletState::Off, 40 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt)
+        );
+        let lamps = OutletGroup::new(vec!["Floor lamp".to_string(), "Desk lamp".to_string()]);
+
+        assert!(lamps.switch_all(&mut room).is_empty());
+        assert_eq!(room.total_power_usage(), 350 as Watt);
+
+        assert!(lamps.turn_on_all(&mut room).is_empty());
+        assert_eq!(room.total_power_usage(), 350 as Watt);
+
+        assert!(lamps.turn_off_all(&mut room).is_empty());
+        assert_eq!(room.total_power_usage(), 250 as Watt);
+        assert_eq!(
+            room.view_device("PC").unwrap().info(),
+            "Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"
+        );
+    }
+
+    #[test]
+    fn outlet_group_not_found_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "Floor lamp" => Device::new_outlet("Floor lamp".to_string(), OutletState::Off, 60 as Watt),
+            "Thermometer" => Device::new_thermometer("Thermometer".to_string(), 22.5 as Celsius)
+        );
+        let group = OutletGroup::new(vec![
+            "Floor lamp".to_string(),
+            "Ceiling lamp".to_string(),
+            "Thermometer".to_string(),
+        ]);
+
+        assert_eq!(
+            group.switch_all(&mut room),
+            vec!["Ceiling lamp".to_string(), "Thermometer".to_string()]
+        );
+        assert_eq!(room.total_power_usage(), 60 as Watt);
+    }
+
+    #[test]
+    fn outlet_group_duplicate_keys_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "Floor lamp" => Device::new_outlet("Floor lamp".to_string(), OutletState::Off, 60 as Watt)
+        );
+        let group = OutletGroup::new(vec![
+            "Floor lamp".to_string(),
+            "Ceiling lamp".to_string(),
+            "Floor lamp".to_string(),
+            "Ceiling lamp".to_string(),
+        ]);
+        assert_eq!(
+            group.keys().collect::<Vec<&str>>(),
+            vec!["Ceiling lamp", "Floor lamp"]
+        );
+
+        assert_eq!(
+            group.switch_all(&mut room),
+            vec!["Ceiling lamp".to_string()]
+        );
+        assert_eq!(room.total_power_usage(), 60 as Watt);
+    }
+}
@@ -0,0 +1,160 @@
+//! Pluggable construction of device types the `Device` enum does not know about.
+//!
+//! A `Registry` maps kind strings, such as the `kind` tag of serialized devices,
+//! to factories building boxed `SmartDevice`s from string parameters.
+
+use crate::traits::SmartDevice;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+
+/// Named string parameters passed to a `DeviceFactory`.
+pub type Params = BTreeMap<String, String>;
+
+/// Returned when a device cannot be built from a kind and its parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FactoryError {
+    pub message: String,
+}
+
+impl fmt::Display for FactoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "smart-home error: {}", self.message)
+    }
+}
+
+impl Error for FactoryError {}
+
+/// Builds devices of a single kind.
+pub trait DeviceFactory {
+    /// Returns the kind string the factory is registered under.
+    fn kind(&self) -> &str;
+
+    /// Builds a device from its parameters.
+    fn build(&self, params: &Params) -> Result<Box<dyn SmartDevice>, FactoryError>;
+}
+
+/// Device factories keyed by their kind.
+#[derive(Default)]
+pub struct Registry {
+    factories: BTreeMap<String, Box<dyn DeviceFactory>>,
+}
+
+impl Registry {
+    /// Creates a registry without any factories.
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    /// Registers a factory under its kind.
+    ///
+    /// # Returns
+    ///
+    /// The factory previously registered for the kind, if any.
+    pub fn register(&mut self, factory: Box<dyn DeviceFactory>) -> Option<Box<dyn DeviceFactory>> {
+        self.factories.insert(factory.kind().to_string(), factory)
+    }
+
+    /// Returns the registered kinds, sorted.
+    pub fn kinds(&self) -> Vec<&str> {
+        self.factories.keys().map(String::as_str).collect()
+    }
+
+    /// Builds a device with the factory registered for `kind`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the new device, or a `FactoryError` if no factory is
+    /// registered for the kind or the factory rejects the parameters.
+    pub fn build(&self, kind: &str, params: &Params) -> Result<Box<dyn SmartDevice>, FactoryError> {
+        let factory = self.factories.get(kind).ok_or_else(|| FactoryError {
+            message: format!("No device factory registered for kind '{}'", kind),
+        })?;
+        factory.build(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::Information;
+    use core::any::Any;
+
+    struct HumiditySensor {
+        name: String,
+        humidity: f64,
+    }
+
+    impl Information for HumiditySensor {
+        fn name(&self) -> String {
+            self.name.clone()
+        }
+
+        fn info(&self) -> String {
+            format!("Humidity Sensor: {} - {:.0}%", self.name, self.humidity)
+        }
+    }
+
+    impl SmartDevice for HumiditySensor {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    struct HumiditySensorFactory;
+
+    impl DeviceFactory for HumiditySensorFactory {
+        fn kind(&self) -> &str {
+            "humidity"
+        }
+
+        fn build(&self, params: &Params) -> Result<Box<dyn SmartDevice>, FactoryError> {
+            let name = params.get("name").ok_or_else(|| FactoryError {
+                message: "Missing parameter 'name'".to_string(),
+            })?;
+            let humidity = params
+                .get("humidity")
+                .and_then(|value| value.parse().ok())
+                .ok_or_else(|| FactoryError {
+                    message: "Missing or invalid parameter 'humidity'".to_string(),
+                })?;
+            Ok(Box::new(HumiditySensor {
+                name: name.clone(),
+                humidity,
+            }))
+        }
+    }
+
+    #[test]
+    fn registry_build_custom_device_test() {
+        let mut registry = Registry::new();
+        assert!(registry.register(Box::new(HumiditySensorFactory)).is_none());
+        assert_eq!(registry.kinds(), ["humidity"]);
+
+        let mut params = Params::new();
+        params.insert("name".to_string(), "Bathroom".to_string());
+        params.insert("humidity".to_string(), "65".to_string());
+        let device = registry.build("humidity", &params).unwrap();
+        assert_eq!(device.info(), "Humidity Sensor: Bathroom - 65%");
+        let sensor = device.as_any().downcast_ref::<HumiditySensor>().unwrap();
+        assert_eq!(sensor.humidity, 65.0);
+
+        params.remove("humidity");
+        assert_eq!(
+            registry
+                .build("humidity", &params)
+                .err()
+                .unwrap()
+                .to_string(),
+            "smart-home error: Missing or invalid parameter 'humidity'"
+        );
+        assert_eq!(
+            registry.build("co2", &params).err().unwrap().to_string(),
+            "smart-home error: No device factory registered for kind 'co2'"
+        );
+    }
+}
@@ -2,18 +2,111 @@ pub mod outlet;
 pub mod thermometer;
 pub mod types;
 
-use crate::traits::Information;
-pub use outlet::{Outlet, OutletDevice, OutletState};
-pub use thermometer::{TemperatureSensor, Thermometer};
+#[cfg(feature = "std")]
+use crate::json;
+use crate::locale::Lang;
+use crate::traits::{Information, SmartDevice};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::error::Error;
+use core::fmt;
+pub use outlet::{
+    Outlet, OutletDevice, OutletState, ParseOutletStateError, PowerUnit, UsageProfile,
+};
+pub use thermometer::{
+    COMFORT_MAX_TEMPERATURE, COMFORT_MIN_TEMPERATURE, Comfort, DEFAULT_PRECISION,
+    TemperatureSensor, Thermometer, Thermostat,
+};
 pub use types::{Celsius, Fahrenheit, Kelvin, Watt};
 
+/// Upper bounds of the rated wattage for the energy labels `'A'` to `'F'`.
+/// Outlets rated above the last bound get `'G'`.
+pub const ENERGY_LABEL_THRESHOLDS: [Watt; 6] = [50, 100, 200, 400, 700, 1000];
+
+/// Energy label letters in order of the thresholds, followed by the label for larger ratings.
+pub const ENERGY_LABELS: [char; 7] = ['A', 'B', 'C', 'D', 'E', 'F', 'G'];
+
+/// With the `serde` feature a device is serialized as its inner fields
+/// tagged with a `kind` of `outlet`, `thermometer` or `empty`.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
 pub enum Device {
+    #[cfg_attr(feature = "serde", serde(rename = "outlet"))]
     OutletType(Outlet),
+    #[cfg_attr(feature = "serde", serde(rename = "thermometer"))]
     ThermometerType(Thermometer),
+    #[cfg_attr(feature = "serde", serde(rename = "empty"))]
     Empty,
 }
 
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum DeviceKind {
+    Outlet,
+    Thermometer,
+    Empty,
+}
+
+impl fmt::Display for DeviceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviceKind::Outlet => write!(f, "Outlet"),
+            DeviceKind::Thermometer => write!(f, "Thermometer"),
+            DeviceKind::Empty => write!(f, "Empty"),
+        }
+    }
+}
+
+/// Returned when a device is accessed as a kind it is not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceTypeError {
+    pub expected: DeviceKind,
+    pub actual: DeviceKind,
+}
+
+impl fmt::Display for DeviceTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "smart-home error: Expected {} device, found {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl Error for DeviceTypeError {}
+
+/// Returned when a device spec of the terse config format cannot be interpreted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpecError {
+    pub message: String,
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "smart-home error: {}", self.message)
+    }
+}
+
+impl Error for SpecError {}
+
+/// Parses the single-character kind codes of the terse config format:
+/// `'o'` for an outlet and `'t'` for a thermometer.
+impl TryFrom<char> for DeviceKind {
+    type Error = SpecError;
+
+    fn try_from(code: char) -> Result<Self, Self::Error> {
+        match code {
+            'o' => Ok(DeviceKind::Outlet),
+            't' => Ok(DeviceKind::Thermometer),
+            _ => Err(SpecError {
+                message: format!("Unknown device kind code '{}', expected 'o' or 't'", code),
+            }),
+        }
+    }
+}
+
 impl From<Outlet> for Device {
     fn from(outlet: Outlet) -> Self {
         Device::OutletType(outlet)
@@ -36,11 +129,7 @@ impl Information for Device {
     }
 
     fn info(&self) -> String {
-        match self {
-            Device::OutletType(outlet) => outlet.info(),
-            Device::ThermometerType(thermometer) => thermometer.info(),
-            Device::Empty => "No device information available".to_string(),
-        }
+        self.info_localized(Lang::En)
     }
 }
 
@@ -52,6 +141,247 @@ impl Device {
     pub fn new_thermometer(name: String, initial_temperature: Celsius) -> Self {
         Device::ThermometerType(Thermometer::new(name, initial_temperature))
     }
+
+    /// Returns the current power draw of the device.
+    ///
+    /// Outlets report their current usage, every other device draws nothing.
+    pub fn power_usage(&self) -> Watt {
+        match self {
+            Device::OutletType(outlet) => outlet.power_usage(),
+            Device::ThermometerType(_) | Device::Empty => 0,
+        }
+    }
+
+    /// Returns the rated power of the device regardless of its current state.
+    ///
+    /// Outlets report their rating, every other device has none.
+    pub fn power_rating(&self) -> Watt {
+        match self {
+            Device::OutletType(outlet) => outlet.power_rating(),
+            Device::ThermometerType(_) | Device::Empty => 0,
+        }
+    }
+
+    /// Grades an outlet by its rated wattage using `ENERGY_LABEL_THRESHOLDS`.
+    ///
+    /// Returns `None` for thermometers and empty devices.
+    pub fn energy_label(&self) -> Option<char> {
+        let Device::OutletType(outlet) = self else {
+            return None;
+        };
+        let grade = ENERGY_LABEL_THRESHOLDS
+            .iter()
+            .position(|&threshold| outlet.power_rating() <= threshold)
+            .unwrap_or(ENERGY_LABEL_THRESHOLDS.len());
+        Some(ENERGY_LABELS[grade])
+    }
+
+    /// Builds a device from a terse spec of a kind code, a name and a value.
+    ///
+    /// For an outlet (`'o'`) the value is its rated power in watts and must be a
+    /// non-negative whole number; the outlet starts switched off. For a thermometer (`'t'`)
+    /// the value is its temperature in Celsius and must be finite.
+    pub fn from_spec(kind: char, name: String, value: f64) -> Result<Device, SpecError> {
+        match DeviceKind::try_from(kind)? {
+            DeviceKind::Outlet => {
+                let power = value as Watt;
+                if power as f64 != value {
+                    return Err(SpecError {
+                        message: format!(
+                            "Invalid power '{}' for outlet '{}', expected a whole number of watts",
+                            value, name
+                        ),
+                    });
+                }
+                Ok(Device::new_outlet(name, OutletState::Off, power))
+            }
+            DeviceKind::Thermometer if value.is_finite() => {
+                Ok(Device::new_thermometer(name, value as Celsius))
+            }
+            DeviceKind::Thermometer => Err(SpecError {
+                message: format!("Invalid temperature '{}' for thermometer '{}'", value, name),
+            }),
+            DeviceKind::Empty => Err(SpecError {
+                message: format!("An empty slot cannot be built from a spec, got '{}'", kind),
+            }),
+        }
+    }
+
+    /// Returns `true` if the device name equals `query`, ignoring case.
+    ///
+    /// Case folding covers all of Unicode, not only ASCII. `Device::Empty` has no name
+    /// and never matches.
+    pub fn matches_name(&self, query: &str) -> bool {
+        match self {
+            Device::Empty => false,
+            device => eq_ignore_case(&device.name(), query),
+        }
+    }
+
+    /// Returns the device report with labels in the given language.
+    pub fn info_localized(&self, lang: Lang) -> String {
+        match self {
+            Device::OutletType(outlet) => outlet.info_localized(lang),
+            Device::ThermometerType(thermometer) => thermometer.info_localized(lang),
+            Device::Empty => lang.labels().no_device.to_string(),
+        }
+    }
+
+    /// Returns the device report with outlet power shown in the given unit.
+    ///
+    /// Thermometers and empty slots have no power value and report as `info()`.
+    pub fn info_with_power_unit(&self, unit: PowerUnit) -> String {
+        match self {
+            Device::OutletType(outlet) => outlet.info_with_power_unit(unit),
+            device => device.info(),
+        }
+    }
+
+    /// Converts the device into a boxed trait object.
+    ///
+    /// Returns `None` for `Device::Empty`, which has no concrete device behind it.
+    pub fn into_smart_device(self) -> Option<Box<dyn SmartDevice>> {
+        match self {
+            Device::OutletType(outlet) => Some(Box::new(outlet)),
+            Device::ThermometerType(thermometer) => Some(Box::new(thermometer)),
+            Device::Empty => None,
+        }
+    }
+
+    /// Renames the wrapped device. `Device::Empty` has no name and is left unchanged.
+    pub fn set_name(&mut self, name: String) {
+        match self {
+            Device::OutletType(outlet) => outlet.set_name(name),
+            Device::ThermometerType(thermometer) => thermometer.set_name(name),
+            Device::Empty => {}
+        }
+    }
+
+    pub fn kind(&self) -> DeviceKind {
+        match self {
+            Device::OutletType(_) => DeviceKind::Outlet,
+            Device::ThermometerType(_) => DeviceKind::Thermometer,
+            Device::Empty => DeviceKind::Empty,
+        }
+    }
+
+    /// Appends the device as a JSON object tagged with its `kind` to `out`.
+    #[cfg(feature = "std")]
+    pub(crate) fn write_json(&self, out: &mut String) {
+        out.push('{');
+        json::write_key(out, "kind");
+        json::write_string(out, &self.kind().to_string().to_lowercase());
+        match self {
+            Device::OutletType(outlet) => {
+                out.push(',');
+                outlet.write_json_fields(out);
+            }
+            Device::ThermometerType(thermometer) => {
+                out.push(',');
+                thermometer.write_json_fields(out);
+            }
+            Device::Empty => {}
+        }
+        out.push('}');
+    }
+
+    /// Returns a copy of the device with outlets switched off.
+    ///
+    /// Thermometers and empty devices are copied unchanged; `self` is never modified.
+    pub fn with_outlet_off(&self) -> Device {
+        let mut device = self.clone();
+        if let Device::OutletType(outlet) = &mut device {
+            outlet.turn_off();
+        }
+        device
+    }
+
+    pub fn new_thermometer_fahrenheit(name: String, initial_temperature: Fahrenheit) -> Self {
+        Device::ThermometerType(Thermometer::from_fahrenheit(name, initial_temperature))
+    }
+
+    /// Returns a terse one-line description, e.g. `"PC [On 250W]"` or `"Bedroom [22.5°C]"`.
+    pub fn summary(&self) -> String {
+        match self {
+            Device::OutletType(outlet) => format!(
+                "{} [{} {}W]",
+                outlet.name(),
+                outlet.state(),
+                outlet.power_usage()
+            ),
+            Device::ThermometerType(thermometer) => format!(
+                "{} [{:.1}°C]",
+                thermometer.name(),
+                thermometer.current_temperature()
+            ),
+            Device::Empty => self.name(),
+        }
+    }
+
+    /// Returns the wrapped outlet for modification.
+    ///
+    /// Fails with a `DeviceTypeError` naming the actual kind when the device is not an outlet.
+    pub fn try_as_outlet_mut(&mut self) -> Result<&mut Outlet, DeviceTypeError> {
+        match self {
+            Device::OutletType(outlet) => Ok(outlet),
+            other => Err(DeviceTypeError {
+                expected: DeviceKind::Outlet,
+                actual: other.kind(),
+            }),
+        }
+    }
+
+    /// Returns the wrapped thermometer for modification.
+    ///
+    /// Fails with a `DeviceTypeError` naming the actual kind when the device is not a thermometer.
+    pub fn try_as_thermometer_mut(&mut self) -> Result<&mut Thermometer, DeviceTypeError> {
+        match self {
+            Device::ThermometerType(thermometer) => Ok(thermometer),
+            other => Err(DeviceTypeError {
+                expected: DeviceKind::Thermometer,
+                actual: other.kind(),
+            }),
+        }
+    }
+
+    /// Dispatches the device to the visitor method matching its kind.
+    pub fn accept<V: DeviceVisitor>(&self, visitor: &mut V) {
+        match self {
+            Device::OutletType(outlet) => visitor.visit_outlet(outlet),
+            Device::ThermometerType(thermometer) => visitor.visit_thermometer(thermometer),
+            Device::Empty => visitor.visit_empty(),
+        }
+    }
+}
+
+/// Operation applied to a device through `Device::accept`, one method per device kind.
+///
+/// Every method does nothing by default, so a visitor only implements the kinds it cares about.
+pub trait DeviceVisitor {
+    fn visit_outlet(&mut self, _outlet: &Outlet) {}
+    fn visit_thermometer(&mut self, _thermometer: &Thermometer) {}
+    fn visit_empty(&mut self) {}
+}
+
+/// Extension trait summing the current power draw of an iterator of devices.
+pub trait PowerSum {
+    /// Returns the combined power usage, saturating at `Watt::MAX` instead of overflowing.
+    fn total_power(self) -> Watt;
+}
+
+impl<'a, I: Iterator<Item = &'a Device>> PowerSum for I {
+    fn total_power(self) -> Watt {
+        self.fold(0, |total: Watt, device| {
+            total.saturating_add(device.power_usage())
+        })
+    }
+}
+
+/// Compares two strings for equality ignoring case, with full Unicode case folding.
+pub(crate) fn eq_ignore_case(a: &str, b: &str) -> bool {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .eq(b.chars().flat_map(char::to_lowercase))
 }
 
 #[cfg(test)]
@@ -180,4 +510,295 @@ mod tests {
             "Thermometer: Test Thermometer - Current Temperature: 25.00°C"
         );
     }
+
+    #[test]
+    fn device_power_usage_test() {
+        let mut outlet = Device::new_outlet("PC".to_string(), OutletState::On, 250);
+        assert_eq!(outlet.power_usage(), 250 as Watt);
+        if let Device::OutletType(ref mut o) = outlet {
+            o.turn_off();
+        }
+        assert_eq!(outlet.power_usage(), 0 as Watt);
+
+        let thermometer = Device::new_thermometer("Bedroom".to_string(), 22.5 as Celsius);
+        assert_eq!(thermometer.power_usage(), 0 as Watt);
+        assert_eq!(Device::Empty.power_usage(), 0 as Watt);
+    }
+
+    #[test]
+    fn device_into_smart_device_test() {
+        let outlet = Outlet::new("PC".to_string(), OutletState::On, 250);
+        let boxed: Box<dyn SmartDevice> = Device::from(outlet.clone()).into_smart_device().unwrap();
+        assert_eq!(boxed.name(), "PC");
+        assert_eq!(boxed.as_any().downcast_ref::<Outlet>(), Some(&outlet));
+        assert!(boxed.as_any().downcast_ref::<Thermometer>().is_none());
+
+        let thermometer = Device::new_thermometer("Bedroom".to_string(), 22.5 as Celsius);
+        let boxed = thermometer.into_smart_device().unwrap();
+        assert_eq!(
+            boxed
+                .as_any()
+                .downcast_ref::<Thermometer>()
+                .unwrap()
+                .current_temperature(),
+            22.5 as Celsius
+        );
+
+        assert!(Device::Empty.into_smart_device().is_none());
+    }
+
+    #[test]
+    fn device_kind_test() {
+        let outlet = Device::new_outlet("PC".to_string(), OutletState::On, 250);
+        let thermometer = Device::new_thermometer("Bedroom".to_string(), 22.5 as Celsius);
+        assert_eq!(outlet.kind(), DeviceKind::Outlet);
+        assert_eq!(thermometer.kind(), DeviceKind::Thermometer);
+        assert_eq!(Device::Empty.kind(), DeviceKind::Empty);
+
+        assert_eq!(DeviceKind::Outlet.to_string(), "Outlet");
+        assert_eq!(DeviceKind::Thermometer.to_string(), "Thermometer");
+        assert_eq!(DeviceKind::Empty.to_string(), "Empty");
+    }
+
+    #[test]
+    fn device_with_outlet_off_test() {
+        let outlet = Device::new_outlet("PC".to_string(), OutletState::On, 250);
+        let preview = outlet.with_outlet_off();
+        assert_eq!(
+            preview.info(),
+            "Smart Outlet: PC - Current State: Off, Power Usage: 0 Watt"
+        );
+        assert_eq!(
+            outlet.info(),
+            "Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"
+        );
+
+        let thermometer = Device::new_thermometer("Bedroom".to_string(), 22.5 as Celsius);
+        assert_eq!(thermometer.with_outlet_off(), thermometer);
+    }
+
+    #[test]
+    fn device_type_thermometer_fahrenheit_test() {
+        let thermometer =
+            Device::new_thermometer_fahrenheit("Porch".to_string(), 68.0 as Fahrenheit);
+        let t: &Thermometer = match thermometer {
+            Device::ThermometerType(ref t) => t,
+            _ => panic!("Expected ThermometerType"),
+        };
+        assert_eq!(t.current_temperature(), 20.0 as Celsius);
+        assert_eq!(
+            thermometer.info(),
+            "Thermometer: Porch - Current Temperature: 20.00°C"
+        );
+
+        let freezing = Thermometer::from_fahrenheit("Freezer".to_string(), 32.0 as Fahrenheit);
+        assert_eq!(freezing.current_temperature(), 0.0 as Celsius);
+    }
+
+    #[test]
+    fn device_summary_test() {
+        let mut outlet = Device::new_outlet("PC".to_string(), OutletState::On, 250);
+        assert_eq!(outlet.summary(), "PC [On 250W]");
+        if let Device::OutletType(ref mut o) = outlet {
+            o.switch();
+        }
+        assert_eq!(outlet.summary(), "PC [Off 0W]");
+
+        let thermometer = Device::new_thermometer("Bedroom".to_string(), 22.5 as Celsius);
+        assert_eq!(thermometer.summary(), "Bedroom [22.5°C]");
+        assert_eq!(Device::Empty.summary(), "No Device");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn device_serde_stable_keys_test() {
+        let outlet = Device::new_outlet("PC".to_string(), OutletState::On, 250);
+        assert_eq!(
+            serde_json::to_value(&outlet).unwrap(),
+            serde_json::json!({
+                "kind": "outlet",
+                "name": "PC",
+                "state": "On",
+                "power_usage": 250,
+                "standby_power": 0,
+                "on_seconds": 0.0,
+                "expected_power": null
+            })
+        );
+
+        let outlet_value = serde_json::to_value(
+            Outlet::new("PC".to_string(), OutletState::On, 250)
+                .with_standby_power(2)
+                .with_expected_power(240),
+        )
+        .unwrap();
+        let outlet_keys: Vec<&str> = outlet_value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            outlet_keys,
+            vec![
+                "expected_power",
+                "name",
+                "on_seconds",
+                "power_usage",
+                "standby_power",
+                "state"
+            ]
+        );
+
+        let thermometer = Device::new_thermometer("Bedroom".to_string(), 22.5 as Celsius);
+        assert_eq!(
+            serde_json::to_value(&thermometer).unwrap(),
+            serde_json::json!({
+                "kind": "thermometer",
+                "name": "Bedroom",
+                "temperature": 22.5
+            })
+        );
+
+        assert_eq!(
+            serde_json::to_value(&Device::Empty).unwrap(),
+            serde_json::json!({ "kind": "empty" })
+        );
+
+        let parsed: Device =
+            serde_json::from_value(serde_json::to_value(&outlet).unwrap()).unwrap();
+        assert_eq!(parsed, outlet);
+    }
+
+    #[test]
+    fn device_try_as_outlet_mut_test() {
+        let mut device = Device::new_outlet("PC".to_string(), OutletState::Off, 250 as Watt);
+        device.try_as_outlet_mut().unwrap().turn_on();
+        assert_eq!(device.power_usage(), 250);
+
+        let mut thermometer = Device::new_thermometer("Bedroom".to_string(), 22.5);
+        let err = thermometer.try_as_outlet_mut().unwrap_err();
+        assert_eq!(err.expected, DeviceKind::Outlet);
+        assert_eq!(err.actual, DeviceKind::Thermometer);
+        assert_eq!(
+            err.to_string(),
+            "smart-home error: Expected Outlet device, found Thermometer"
+        );
+        assert_eq!(
+            Device::Empty.try_as_outlet_mut().unwrap_err().actual,
+            DeviceKind::Empty
+        );
+    }
+
+    #[test]
+    fn device_visitor_test() {
+        #[derive(Default)]
+        struct KindTally {
+            outlets: usize,
+            thermometers: usize,
+            rated_power: Watt,
+        }
+
+        impl DeviceVisitor for KindTally {
+            fn visit_outlet(&mut self, outlet: &Outlet) {
+                self.outlets += 1;
+                self.rated_power += outlet.power_rating();
+            }
+
+            fn visit_thermometer(&mut self, _thermometer: &Thermometer) {
+                self.thermometers += 1;
+            }
+        }
+
+        let devices = [
+            Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            Device::new_thermometer("Bedroom".to_string(), 22.5),
+            Device::new_outlet("Lighter".to_string(), OutletState::Off, 100 as Watt),
+            Device::Empty,
+        ];
+        let mut tally = KindTally::default();
+        for device in &devices {
+            device.accept(&mut tally);
+        }
+        assert_eq!(tally.outlets, 2);
+        assert_eq!(tally.thermometers, 1);
+        assert_eq!(tally.rated_power, 350);
+    }
+
+    #[test]
+    fn device_energy_label_test() {
+        let lamp = Device::new_outlet("Lamp".to_string(), OutletState::Off, 40 as Watt);
+        assert_eq!(lamp.energy_label(), Some('A'));
+        let pc = Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt);
+        assert_eq!(pc.energy_label(), Some('D'));
+        let bound = Device::new_outlet("Iron".to_string(), OutletState::On, 1000 as Watt);
+        assert_eq!(bound.energy_label(), Some('F'));
+        let heater = Device::new_outlet("Heater".to_string(), OutletState::On, 2000 as Watt);
+        assert_eq!(heater.energy_label(), Some('G'));
+
+        let thermometer = Device::new_thermometer("Bedroom".to_string(), 22.5);
+        assert_eq!(thermometer.energy_label(), None);
+        assert_eq!(Device::Empty.energy_label(), None);
+    }
+
+    #[test]
+    fn device_kind_try_from_char_test() {
+        assert_eq!(DeviceKind::try_from('o'), Ok(DeviceKind::Outlet));
+        assert_eq!(DeviceKind::try_from('t'), Ok(DeviceKind::Thermometer));
+        assert_eq!(
+            DeviceKind::try_from('x').unwrap_err().to_string(),
+            "smart-home error: Unknown device kind code 'x', expected 'o' or 't'"
+        );
+    }
+
+    #[test]
+    fn device_from_spec_test() {
+        let outlet = Device::from_spec('o', "PC".to_string(), 250.0).unwrap();
+        assert_eq!(
+            outlet,
+            Device::new_outlet("PC".to_string(), OutletState::Off, 250 as Watt)
+        );
+        let thermometer = Device::from_spec('t', "Bedroom".to_string(), 22.5).unwrap();
+        assert_eq!(
+            thermometer,
+            Device::new_thermometer("Bedroom".to_string(), 22.5)
+        );
+
+        assert!(Device::from_spec('x', "Lamp".to_string(), 40.0).is_err());
+        assert!(Device::from_spec('o', "Lamp".to_string(), -40.0).is_err());
+        assert!(Device::from_spec('o', "Lamp".to_string(), 40.5).is_err());
+        assert!(Device::from_spec('t', "Hall".to_string(), f64::NAN).is_err());
+    }
+
+    #[test]
+    fn device_matches_name_test() {
+        let pc = Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt);
+        assert!(pc.matches_name("pc"));
+        assert!(pc.matches_name("PC"));
+        assert!(pc.matches_name("Pc"));
+        assert!(!pc.matches_name("pc "));
+        assert!(!pc.matches_name("laptop"));
+
+        let thermometer = Device::new_thermometer("Спальня".to_string(), 22.5);
+        assert!(thermometer.matches_name("СПАЛЬНЯ"));
+        assert!(!Device::Empty.matches_name("No Device"));
+    }
+
+    #[test]
+    fn device_info_with_power_unit_test() {
+        let oven = Device::new_outlet("Oven".to_string(), OutletState::On, 3000 as Watt);
+        assert_eq!(
+            oven.info_with_power_unit(PowerUnit::Watt),
+            "Smart Outlet: Oven - Current State: On, Power Usage: 3000 Watt"
+        );
+        assert_eq!(
+            oven.info_with_power_unit(PowerUnit::Kilowatt),
+            "Smart Outlet: Oven - Current State: On, Power Usage: 3.0 kW"
+        );
+        let thermometer = Device::new_thermometer("Kitchen".to_string(), 20.0);
+        assert_eq!(
+            thermometer.info_with_power_unit(PowerUnit::Kilowatt),
+            thermometer.info()
+        );
+    }
 }
@@ -1,10 +1,21 @@
 use super::types::Watt;
-use crate::traits::Information;
+#[cfg(feature = "std")]
+use crate::json;
+use crate::locale::Lang;
+use crate::traits::{Information, SmartDevice};
 
-use std::fmt;
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+use core::any::Any;
+use core::error::Error;
+use core::fmt;
+use core::str::FromStr;
 
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OutletState {
     On,
     Off,
@@ -19,6 +30,44 @@ impl fmt::Display for OutletState {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOutletStateError {
+    pub message: String,
+}
+
+impl fmt::Display for ParseOutletStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "smart-home error: {}", self.message)
+    }
+}
+
+impl Error for ParseOutletStateError {}
+
+impl FromStr for OutletState {
+    type Err = ParseOutletStateError;
+
+    /// Parses an outlet state from `"on"` or `"off"`, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("on") {
+            Ok(OutletState::On)
+        } else if s.eq_ignore_ascii_case("off") {
+            Ok(OutletState::Off)
+        } else {
+            Err(ParseOutletStateError {
+                message: format!("Unknown outlet state '{}', expected 'On' or 'Off'", s),
+            })
+        }
+    }
+}
+
+impl TryFrom<&str> for OutletState {
+    type Error = ParseOutletStateError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
 pub trait OutletDevice: Information {
     fn turn_on(&mut self);
     fn turn_off(&mut self);
@@ -27,11 +76,67 @@ pub trait OutletDevice: Information {
     fn power_usage(&self) -> Watt;
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Unit used for power values in outlet reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PowerUnit {
+    #[default]
+    Watt,
+    Kilowatt,
+}
+
+/// Share of each hour of a day, from midnight, during which an outlet is on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UsageProfile {
+    multipliers: [f64; 24],
+}
+
+impl UsageProfile {
+    /// Creates a profile from 24 hourly multipliers.
+    ///
+    /// Returns `None` if any multiplier lies outside `0.0..=1.0`.
+    pub fn new(multipliers: [f64; 24]) -> Option<Self> {
+        multipliers
+            .iter()
+            .all(|multiplier| (0.0..=1.0).contains(multiplier))
+            .then_some(UsageProfile { multipliers })
+    }
+
+    pub fn multipliers(&self) -> &[f64; 24] {
+        &self.multipliers
+    }
+}
+
+/// With the `serde` feature the outlet is serialized with stable snake_case keys.
+/// Every key is part of the wire format and keeps its name even if the Rust fields
+/// are renamed.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct Outlet {
+    #[cfg_attr(feature = "serde", serde(rename = "name"))]
     name: String,
+    #[cfg_attr(feature = "serde", serde(rename = "state"))]
     state: OutletState,
+    #[cfg_attr(feature = "serde", serde(rename = "power_usage"))]
     power_usage: Watt,
+    #[cfg_attr(feature = "serde", serde(rename = "standby_power", default))]
+    standby_power: Watt,
+    #[cfg_attr(feature = "serde", serde(rename = "on_seconds", default))]
+    on_seconds: f64,
+    #[cfg_attr(feature = "serde", serde(rename = "expected_power", default))]
+    expected_power: Option<Watt>,
+}
+
+/// Outlets are equal when they have the same name, state and power characteristics. The
+/// accumulated runtime and the expected power used for anomaly detection are monitoring
+/// state and do not take part in the comparison.
+impl PartialEq for Outlet {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.state == other.state
+            && self.power_usage == other.power_usage
+            && self.standby_power == other.standby_power
+    }
 }
 
 impl Outlet {
@@ -40,8 +145,137 @@ impl Outlet {
             name,
             state: initial_state,
             power_usage,
+            standby_power: 0,
+            on_seconds: 0.0,
+            expected_power: None,
+        }
+    }
+
+    /// Sets the power the outlet draws while switched off.
+    pub fn with_standby_power(mut self, standby_power: Watt) -> Self {
+        self.standby_power = standby_power;
+        self
+    }
+
+    /// Sets the rated power of the outlet.
+    pub fn with_power(mut self, watts: Watt) -> Self {
+        self.power_usage = watts;
+        self
+    }
+
+    pub fn standby_power(&self) -> Watt {
+        self.standby_power
+    }
+
+    /// Returns the rated power of the outlet, i.e. its draw when on, whatever its current state.
+    pub fn power_rating(&self) -> Watt {
+        self.power_usage
+    }
+
+    /// Returns the outlet report with labels in the given language.
+    pub fn info_localized(&self, lang: Lang) -> String {
+        self.report(lang, PowerUnit::Watt)
+    }
+
+    /// Returns the outlet report with the power usage shown in the given unit.
+    ///
+    /// Kilowatts are shown with one decimal place, e.g. `3.0 kW`. `info()` always uses watts.
+    pub fn info_with_power_unit(&self, unit: PowerUnit) -> String {
+        self.report(Lang::En, unit)
+    }
+
+    fn report(&self, lang: Lang, unit: PowerUnit) -> String {
+        let labels = lang.labels();
+        let state = match self.state {
+            OutletState::On => labels.on,
+            OutletState::Off => labels.off,
+        };
+        let usage = OutletDevice::power_usage(self);
+        let power = match unit {
+            PowerUnit::Watt => format!("{} {}", usage, labels.watt),
+            PowerUnit::Kilowatt => format!("{:.1} kW", usage as f64 / 1000.0),
+        };
+        format!(
+            "{}: {} - {}: {}, {}: {}",
+            labels.smart_outlet, self.name, labels.current_state, state, labels.power_usage, power
+        )
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    /// Adds the elapsed time to the runtime counter if the outlet is currently on.
+    ///
+    /// Negative and non-finite durations are ignored.
+    pub fn accumulate_runtime(&mut self, seconds: f64) {
+        if self.state == OutletState::On && seconds.is_finite() && seconds >= 0.0 {
+            self.on_seconds += seconds;
+        }
+    }
+
+    pub fn total_on_seconds(&self) -> f64 {
+        self.on_seconds
+    }
+
+    /// Appends the outlet fields as JSON object members to `out`.
+    #[cfg(feature = "std")]
+    pub(crate) fn write_json_fields(&self, out: &mut String) {
+        json::write_key(out, "name");
+        json::write_string(out, &self.name);
+        out.push(',');
+        json::write_key(out, "state");
+        json::write_string(out, &self.state.to_string());
+        out.push(',');
+        json::write_key(out, "power_usage");
+        out.push_str(&self.power_usage.to_string());
+        out.push(',');
+        json::write_key(out, "standby_power");
+        out.push_str(&self.standby_power.to_string());
+        out.push(',');
+        json::write_key(out, "on_seconds");
+        json::write_f64(out, self.on_seconds);
+        out.push(',');
+        json::write_key(out, "expected_power");
+        match self.expected_power {
+            Some(expected_power) => out.push_str(&expected_power.to_string()),
+            None => out.push_str("null"),
         }
     }
+
+    /// Projects the energy the outlet uses over a day following the usage profile.
+    ///
+    /// For each hour the outlet is on for the profile share of the hour, drawing its rating,
+    /// and off for the rest, drawing its standby power. The current state is not used.
+    pub fn projected_daily_kwh(&self, profile: &UsageProfile) -> f64 {
+        let watt_hours: f64 = profile
+            .multipliers()
+            .iter()
+            .map(|on_share| {
+                self.power_usage as f64 * on_share + self.standby_power as f64 * (1.0 - on_share)
+            })
+            .sum();
+        watt_hours / 1000.0
+    }
+
+    /// Sets the power the outlet is expected to draw, used for anomaly detection.
+    pub fn with_expected_power(mut self, expected_power: Watt) -> Self {
+        self.expected_power = Some(expected_power);
+        self
+    }
+
+    pub fn expected_power(&self) -> Option<Watt> {
+        self.expected_power
+    }
+
+    /// Returns the signed difference between the current draw and the expected power,
+    /// clamped to the `i64` range, or `None` if no expected power is set.
+    pub fn power_anomaly(&self) -> Option<i64> {
+        self.expected_power.map(|expected| {
+            let delta = OutletDevice::power_usage(self) as i128 - expected as i128;
+            delta.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+        })
+    }
 }
 
 impl Information for Outlet {
@@ -49,11 +283,13 @@ impl Information for Outlet {
         self.name.clone()
     }
     fn info(&self) -> String {
-        let usage = OutletDevice::power_usage(self);
-        format!(
-            "Smart Outlet: {} - Current State: {}, Power Usage: {} Watt",
-            self.name, self.state, usage
-        )
+        self.info_localized(Lang::En)
+    }
+}
+
+impl SmartDevice for Outlet {
+    fn as_any(&self) -> &dyn Any {
+        self
     }
 }
 
@@ -80,7 +316,7 @@ impl OutletDevice for Outlet {
     fn power_usage(&self) -> Watt {
         match self.state {
             OutletState::On => self.power_usage,
-            OutletState::Off => 0,
+            OutletState::Off => self.standby_power,
         }
     }
 }
@@ -88,6 +324,7 @@ impl OutletDevice for Outlet {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::string::ToString;
 
     #[test]
     fn outlet_create_test() {
@@ -134,4 +371,227 @@ mod tests {
             "Smart Outlet: Living Room Outlet - Current State: Off, Power Usage: 0 Watt"
         );
     }
+
+    #[test]
+    fn outlet_state_from_str_test() {
+        assert_eq!("On".parse::<OutletState>(), Ok(OutletState::On));
+        assert_eq!("on".parse::<OutletState>(), Ok(OutletState::On));
+        assert_eq!("OFF".parse::<OutletState>(), Ok(OutletState::Off));
+        assert_eq!("off".parse::<OutletState>(), Ok(OutletState::Off));
+        assert_eq!(
+            "On".parse::<OutletState>().unwrap().to_string(),
+            OutletState::On.to_string()
+        );
+    }
+
+    #[test]
+    fn outlet_state_try_from_test() {
+        assert_eq!(OutletState::try_from("oN"), Ok(OutletState::On));
+        assert_eq!(OutletState::try_from("Off"), Ok(OutletState::Off));
+    }
+
+    #[test]
+    fn outlet_state_parse_error_test() {
+        let err = OutletState::try_from("maybe").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "smart-home error: Unknown outlet state 'maybe', expected 'On' or 'Off'"
+        );
+        assert!("maybe".parse::<OutletState>().is_err());
+    }
+
+    #[test]
+    fn outlet_info_localized_test() {
+        let outlet = Outlet::new("PC".to_string(), OutletState::On, 250);
+        assert_eq!(outlet.info_localized(Lang::En), outlet.info());
+        assert_eq!(
+            outlet.info_localized(Lang::Ru),
+            "Умная розетка: PC - Текущее состояние: Вкл, Потребляемая мощность: 250 Вт"
+        );
+    }
+
+    #[test]
+    fn outlet_standby_power_test() {
+        let mut outlet = Outlet::new("TV".to_string(), OutletState::Off, 120).with_standby_power(5);
+        assert_eq!(outlet.standby_power(), 5);
+        assert_eq!(outlet.power_usage(), 5);
+        assert_eq!(
+            outlet.info(),
+            "Smart Outlet: TV - Current State: Off, Power Usage: 5 Watt"
+        );
+        outlet.turn_on();
+        assert_eq!(outlet.power_usage(), 120);
+        outlet.turn_off();
+        assert_eq!(outlet.power_usage(), 5);
+
+        let outlet = Outlet::new("Lamp".to_string(), OutletState::Off, 60);
+        assert_eq!(outlet.standby_power(), 0);
+        assert_eq!(outlet.power_usage(), 0);
+    }
+
+    #[test]
+    fn outlet_runtime_test() {
+        let mut outlet = Outlet::new("Heater".to_string(), OutletState::On, 1000);
+        assert_eq!(outlet.total_on_seconds(), 0.0);
+        outlet.accumulate_runtime(60.0);
+        assert_eq!(outlet.total_on_seconds(), 60.0);
+
+        outlet.switch();
+        outlet.accumulate_runtime(120.0);
+        assert_eq!(outlet.total_on_seconds(), 60.0);
+
+        outlet.switch();
+        outlet.accumulate_runtime(30.5);
+        assert_eq!(outlet.total_on_seconds(), 90.5);
+
+        outlet.accumulate_runtime(-10.0);
+        outlet.accumulate_runtime(f64::NAN);
+        outlet.accumulate_runtime(f64::INFINITY);
+        assert_eq!(outlet.total_on_seconds(), 90.5);
+    }
+
+    #[test]
+    fn outlet_eq_ignores_runtime_test() {
+        let mut outlet = Outlet::new("Heater".to_string(), OutletState::On, 1000);
+        outlet.accumulate_runtime(60.0);
+        assert_eq!(
+            outlet,
+            Outlet::new("Heater".to_string(), OutletState::On, 1000)
+        );
+        assert_ne!(
+            outlet,
+            Outlet::new("Heater".to_string(), OutletState::Off, 1000)
+        );
+        assert_ne!(
+            outlet,
+            Outlet::new("Heater".to_string(), OutletState::On, 1000).with_standby_power(2)
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            let restored: Outlet = serde_json::from_str(
+                r#"{"name":"Heater","state":"On","power_usage":1000,"standby_power":0,"expected_power":null}"#,
+            )
+            .unwrap();
+            assert_eq!(restored.total_on_seconds(), 0.0);
+            assert_eq!(restored, outlet);
+        }
+    }
+
+    #[test]
+    fn outlet_power_anomaly_test() {
+        let outlet = Outlet::new("Fridge".to_string(), OutletState::On, 180);
+        assert_eq!(outlet.expected_power(), None);
+        assert_eq!(outlet.power_anomaly(), None);
+
+        let mut outlet = outlet.with_expected_power(150);
+        assert_eq!(outlet.expected_power(), Some(150));
+        assert_eq!(outlet.power_anomaly(), Some(30));
+
+        outlet.turn_off();
+        assert_eq!(outlet.power_anomaly(), Some(-150));
+
+        let huge =
+            Outlet::new("Plant".to_string(), OutletState::On, Watt::MAX).with_expected_power(0);
+        assert_eq!(huge.power_anomaly(), Some(i64::MAX));
+        let silent =
+            Outlet::new("Plant".to_string(), OutletState::Off, 0).with_expected_power(Watt::MAX);
+        assert_eq!(silent.power_anomaly(), Some(i64::MIN));
+        assert_eq!(
+            silent,
+            Outlet::new("Plant".to_string(), OutletState::Off, 0)
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            let restored: Outlet = serde_json::from_str(
+                r#"{"name":"Fridge","state":"On","power_usage":180,"standby_power":0,"on_seconds":0.0}"#,
+            )
+            .unwrap();
+            assert_eq!(restored.expected_power(), None);
+        }
+    }
+
+    #[test]
+    fn outlet_power_rating_test() {
+        let mut outlet = Outlet::new("Heater".to_string(), OutletState::Off, 1500 as Watt);
+        assert_eq!(outlet.power_rating(), 1500);
+        assert_eq!(outlet.power_usage(), 0);
+        outlet.turn_on();
+        assert_eq!(outlet.power_rating(), 1500);
+    }
+
+    #[test]
+    fn outlet_projected_daily_kwh_test() {
+        let outlet = Outlet::new("Fridge".to_string(), OutletState::Off, 100 as Watt);
+        let always_on = UsageProfile::new([1.0; 24]).unwrap();
+        assert_eq!(outlet.projected_daily_kwh(&always_on), 2.4);
+        let half_time = UsageProfile::new([0.5; 24]).unwrap();
+        assert_eq!(outlet.projected_daily_kwh(&half_time), 1.2);
+
+        let mut evening = [0.0; 24];
+        evening[18..22].copy_from_slice(&[0.5, 1.0, 1.0, 0.5]);
+        let evening = UsageProfile::new(evening).unwrap();
+        assert_eq!(outlet.projected_daily_kwh(&evening), 0.3);
+
+        let with_standby = outlet.with_standby_power(5);
+        let expected = 0.3 + 0.105;
+        assert!((with_standby.projected_daily_kwh(&evening) - expected).abs() < 1e-9);
+
+        assert!(UsageProfile::new([1.5; 24]).is_none());
+        assert!(UsageProfile::new([f64::NAN; 24]).is_none());
+    }
+
+    #[test]
+    fn outlet_with_power_test() {
+        let outlet = Outlet::new("PC".to_string(), OutletState::On, 250 as Watt)
+            .with_power(300)
+            .with_standby_power(5);
+        assert_eq!(outlet.power_rating(), 300);
+        assert_eq!(
+            outlet.info(),
+            "Smart Outlet: PC - Current State: On, Power Usage: 300 Watt"
+        );
+        let mut off = outlet.with_power(120);
+        off.turn_off();
+        assert_eq!(
+            off.info(),
+            "Smart Outlet: PC - Current State: Off, Power Usage: 5 Watt"
+        );
+        off.turn_on();
+        assert_eq!(off.power_usage(), 120);
+    }
+
+    #[test]
+    fn outlet_info_with_power_unit_test() {
+        let outlet = Outlet::new("Oven".to_string(), OutletState::On, 3000 as Watt);
+        assert_eq!(
+            outlet.info_with_power_unit(PowerUnit::Watt),
+            "Smart Outlet: Oven - Current State: On, Power Usage: 3000 Watt"
+        );
+        assert_eq!(
+            outlet.info_with_power_unit(PowerUnit::default()),
+            outlet.info()
+        );
+        assert_eq!(
+            outlet.info_with_power_unit(PowerUnit::Kilowatt),
+            "Smart Outlet: Oven - Current State: On, Power Usage: 3.0 kW"
+        );
+        let small = Outlet::new("Lamp".to_string(), OutletState::On, 60 as Watt);
+        assert_eq!(
+            small.info_with_power_unit(PowerUnit::Kilowatt),
+            "Smart Outlet: Lamp - Current State: On, Power Usage: 0.1 kW"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn outlet_deserialize_without_standby_power_test() {
+        let outlet: Outlet = serde_json::from_str(
+            r#"{"name":"PC","state":"On","power_usage":250,"on_seconds":0.0,"expected_power":null}"#,
+        )
+        .unwrap();
+        assert_eq!(outlet.standby_power(), 0);
+        assert_eq!(outlet, Outlet::new("PC".to_string(), OutletState::On, 250));
+    }
 }
@@ -1,14 +1,75 @@
-use super::types::Celsius;
-use crate::traits::Information;
+use super::outlet::OutletState;
+use super::types::{Celsius, Fahrenheit};
+#[cfg(feature = "std")]
+use crate::json;
+use crate::locale::Lang;
+use crate::traits::{Information, SmartDevice};
+
+use alloc::format;
+use alloc::string::String;
+use core::any::Any;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::time::{Duration, SystemTime};
+
+/// Temperatures below this value are classified as `Comfort::Cold`.
+pub const COMFORT_MIN_TEMPERATURE: Celsius = 18.0;
+/// Temperatures above this value are classified as `Comfort::Hot`.
+pub const COMFORT_MAX_TEMPERATURE: Celsius = 26.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comfort {
+    Cold,
+    Comfortable,
+    Hot,
+}
+
+impl fmt::Display for Comfort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Comfort::Cold => write!(f, "Cold"),
+            Comfort::Comfortable => write!(f, "Comfortable"),
+            Comfort::Hot => write!(f, "Hot"),
+        }
+    }
+}
 
 pub trait TemperatureSensor: Information {
     fn current_temperature(&self) -> Celsius;
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Number of decimal places used for the temperature in `info()` unless changed
+/// with `Thermometer::with_precision`.
+pub const DEFAULT_PRECISION: usize = 2;
+
+fn default_precision() -> usize {
+    DEFAULT_PRECISION
+}
+
+/// With the `serde` feature the thermometer is serialized with stable snake_case keys.
+/// `temperature` is part of the wire format; the local update time and the display
+/// precision are not serialized. The update time is only tracked with the `std` feature.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct Thermometer {
+    #[cfg_attr(feature = "serde", serde(rename = "name"))]
     name: String,
+    #[cfg_attr(feature = "serde", serde(rename = "temperature"))]
     temperature: Celsius,
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_updated: Option<SystemTime>,
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_precision"))]
+    precision: usize,
+}
+
+/// Thermometers are equal when they have the same name and reading. Like the update
+/// time, the display precision is not serialized and does not take part in the comparison.
+impl PartialEq for Thermometer {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.temperature == other.temperature
+    }
 }
 
 impl Thermometer {
@@ -16,8 +77,105 @@ impl Thermometer {
         Thermometer {
             name,
             temperature: initial_temperature,
+            #[cfg(feature = "std")]
+            last_updated: None,
+            precision: default_precision(),
         }
     }
+
+    /// Sets the number of decimal places shown for the temperature in `info()`.
+    pub fn with_precision(mut self, digits: usize) -> Self {
+        self.precision = digits;
+        self
+    }
+
+    /// Sets the current reading. Unlike `set_temperature`, no update time is recorded.
+    pub fn with_temperature(mut self, temp: Celsius) -> Self {
+        self.temperature = temp;
+        self
+    }
+
+    /// Returns the thermometer report with labels in the given language.
+    pub fn info_localized(&self, lang: Lang) -> String {
+        let labels = lang.labels();
+        format!(
+            "{}: {} - {}: {:.*}°C",
+            labels.thermometer,
+            self.name,
+            labels.current_temperature,
+            self.precision,
+            self.temperature
+        )
+    }
+
+    /// Classifies the current temperature using the comfort thresholds.
+    pub fn comfort(&self) -> Comfort {
+        if self.temperature < COMFORT_MIN_TEMPERATURE {
+            Comfort::Cold
+        } else if self.temperature > COMFORT_MAX_TEMPERATURE {
+            Comfort::Hot
+        } else {
+            Comfort::Comfortable
+        }
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    /// Appends the thermometer fields as JSON object members to `out`.
+    #[cfg(feature = "std")]
+    pub(crate) fn write_json_fields(&self, out: &mut String) {
+        json::write_key(out, "name");
+        json::write_string(out, &self.name);
+        out.push(',');
+        json::write_key(out, "temperature");
+        json::write_f64(out, self.temperature);
+    }
+
+    /// Stores a new reading and records when it was taken.
+    pub fn set_temperature(&mut self, temperature: Celsius) {
+        self.temperature = temperature;
+        #[cfg(feature = "std")]
+        {
+            self.last_updated = Some(SystemTime::now());
+        }
+    }
+
+    /// Shifts the current reading by `delta`, e.g. to apply a calibration offset.
+    ///
+    /// Unlike `set_temperature` this is not a new reading, so the update time is kept.
+    pub fn offset_temperature(&mut self, delta: Celsius) {
+        self.temperature += delta;
+    }
+
+    /// Stores a new reading and reports whether it crossed `threshold`.
+    ///
+    /// A reading at or above the threshold counts as above it. Returns `true` when the
+    /// previous and the new reading lie on different sides of the threshold.
+    pub fn record(&mut self, temperature: Celsius, threshold: Celsius) -> bool {
+        let was_above = self.temperature >= threshold;
+        self.set_temperature(temperature);
+        was_above != (temperature >= threshold)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn last_updated(&self) -> Option<SystemTime> {
+        self.last_updated
+    }
+
+    /// Returns the time elapsed since the last `set_temperature` call,
+    /// or `None` if the reading was never updated after construction.
+    #[cfg(feature = "std")]
+    pub fn age(&self) -> Option<Duration> {
+        self.last_updated
+            .map(|updated| updated.elapsed().unwrap_or_default())
+    }
+
+    /// Creates a thermometer from a Fahrenheit reading, stored internally in Celsius.
+    pub fn from_fahrenheit(name: String, initial_temperature: Fahrenheit) -> Self {
+        Thermometer::new(name, (initial_temperature - 32.0) * 5.0 / 9.0)
+    }
 }
 
 impl Information for Thermometer {
@@ -25,10 +183,13 @@ impl Information for Thermometer {
         self.name.clone()
     }
     fn info(&self) -> String {
-        format!(
-            "Thermometer: {} - Current Temperature: {:.2}°C",
-            self.name, self.temperature
-        )
+        self.info_localized(Lang::En)
+    }
+}
+
+impl SmartDevice for Thermometer {
+    fn as_any(&self) -> &dyn Any {
+        self
     }
 }
 
@@ -38,9 +199,64 @@ impl TemperatureSensor for Thermometer {
     }
 }
 
+/// Drives a heater outlet from temperature readings.
+///
+/// The heater is turned on below `target - hysteresis` and off above
+/// `target + hysteresis`. Inside the band the heater keeps its current state, so a
+/// reading hovering around the target does not make it chatter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Thermostat {
+    heater: String,
+    target: Celsius,
+    hysteresis: Celsius,
+}
+
+impl Thermostat {
+    /// Creates a thermostat for the heater outlet with the given key.
+    ///
+    /// The sign of `hysteresis` is ignored.
+    pub fn new(heater: String, target: Celsius, hysteresis: Celsius) -> Self {
+        Thermostat {
+            heater,
+            target,
+            hysteresis: hysteresis.abs(),
+        }
+    }
+
+    /// Returns the key of the linked heater outlet.
+    pub fn heater(&self) -> &str {
+        &self.heater
+    }
+
+    pub fn target(&self) -> Celsius {
+        self.target
+    }
+
+    pub fn hysteresis(&self) -> Celsius {
+        self.hysteresis
+    }
+
+    /// Decides what the heater should do for the given reading.
+    ///
+    /// # Returns
+    ///
+    /// `Some(OutletState::On)` below the band, `Some(OutletState::Off)` above it and `None`
+    /// when the reading is inside the band, bounds included.
+    pub fn evaluate(&self, current: Celsius) -> Option<OutletState> {
+        if current < self.target - self.hysteresis {
+            Some(OutletState::On)
+        } else if current > self.target + self.hysteresis {
+            Some(OutletState::Off)
+        } else {
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::string::ToString;
 
     #[test]
     fn thermometer_create_test() {
@@ -52,4 +268,141 @@ mod tests {
             "Thermometer: Living Room - Current Temperature: 22.50°C"
         );
     }
+
+    #[test]
+    fn thermometer_comfort_test() {
+        let cold = Thermometer::new("Balcony".to_string(), 15.0 as Celsius);
+        assert_eq!(cold.comfort(), Comfort::Cold);
+        assert_eq!(cold.comfort().to_string(), "Cold");
+
+        let comfortable = Thermometer::new("Bedroom".to_string(), 22.0 as Celsius);
+        assert_eq!(comfortable.comfort(), Comfort::Comfortable);
+
+        let hot = Thermometer::new("Sauna".to_string(), 30.0 as Celsius);
+        assert_eq!(hot.comfort(), Comfort::Hot);
+
+        let lower_bound = Thermometer::new("Hall".to_string(), COMFORT_MIN_TEMPERATURE);
+        assert_eq!(lower_bound.comfort(), Comfort::Comfortable);
+        let upper_bound = Thermometer::new("Hall".to_string(), COMFORT_MAX_TEMPERATURE);
+        assert_eq!(upper_bound.comfort(), Comfort::Comfortable);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn thermometer_set_temperature_age_test() {
+        let mut thermometer = Thermometer::new("Living Room".to_string(), 22.5 as Celsius);
+        assert_eq!(thermometer.age(), None);
+        assert_eq!(thermometer.last_updated(), None);
+
+        thermometer.set_temperature(23.0 as Celsius);
+        assert_eq!(thermometer.current_temperature(), 23.0 as Celsius);
+        assert!(thermometer.last_updated().is_some());
+        let age = thermometer.age().unwrap();
+        assert!(age < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn thermometer_with_precision_test() {
+        let thermometer = Thermometer::new("Bedroom".to_string(), 22.5);
+        assert_eq!(
+            thermometer.info(),
+            "Thermometer: Bedroom - Current Temperature: 22.50°C"
+        );
+        assert_eq!(
+            thermometer.clone().with_precision(1).info(),
+            "Thermometer: Bedroom - Current Temperature: 22.5°C"
+        );
+        let precise = thermometer.clone().with_precision(3);
+        assert_eq!(
+            precise.info(),
+            "Thermometer: Bedroom - Current Temperature: 22.500°C"
+        );
+        assert_eq!(precise, thermometer);
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&precise).unwrap();
+            let restored: Thermometer = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored, precise);
+            assert_eq!(restored.info(), thermometer.info());
+        }
+    }
+
+    #[test]
+    fn thermometer_record_test() {
+        let mut thermometer = Thermometer::new("Bedroom".to_string(), 20.0);
+        assert!(!thermometer.record(22.0, 25.0));
+        assert_eq!(thermometer.current_temperature(), 22.0);
+
+        assert!(thermometer.record(26.0, 25.0));
+        assert!(!thermometer.record(27.5, 25.0));
+
+        assert!(thermometer.record(18.0, 25.0));
+        assert_eq!(thermometer.current_temperature(), 18.0);
+
+        assert!(thermometer.record(25.0, 25.0));
+        assert!(!thermometer.record(25.0, 25.0));
+    }
+
+    #[test]
+    fn thermostat_evaluate_test() {
+        let thermostat = Thermostat::new("Heater".to_string(), 21.0, 0.5);
+        assert_eq!(thermostat.heater(), "Heater");
+
+        assert_eq!(thermostat.evaluate(19.0), Some(OutletState::On));
+        assert_eq!(thermostat.evaluate(20.4), Some(OutletState::On));
+
+        assert_eq!(thermostat.evaluate(20.5), None);
+        assert_eq!(thermostat.evaluate(21.0), None);
+        assert_eq!(thermostat.evaluate(21.5), None);
+
+        assert_eq!(thermostat.evaluate(21.6), Some(OutletState::Off));
+        assert_eq!(thermostat.evaluate(25.0), Some(OutletState::Off));
+
+        let negative = Thermostat::new("Heater".to_string(), 21.0, -0.5);
+        assert_eq!(negative.hysteresis(), 0.5);
+        assert_eq!(negative.evaluate(21.0), None);
+    }
+
+    #[test]
+    fn thermometer_with_temperature_test() {
+        let thermometer = Thermometer::new("Bedroom".to_string(), 20.0)
+            .with_temperature(23.25)
+            .with_precision(1)
+            .with_temperature(24.0);
+        assert_eq!(thermometer.current_temperature(), 24.0);
+        assert_eq!(
+            thermometer.info(),
+            "Thermometer: Bedroom - Current Temperature: 24.0°C"
+        );
+    }
+
+    #[test]
+    fn thermometer_offset_temperature_test() {
+        let mut thermometer = Thermometer::new("Bedroom".to_string(), 20.0);
+        thermometer.offset_temperature(1.5);
+        assert_eq!(thermometer.current_temperature(), 21.5);
+        thermometer.offset_temperature(-2.0);
+        assert_eq!(thermometer.current_temperature(), 19.5);
+        #[cfg(feature = "std")]
+        assert_eq!(thermometer.last_updated(), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn thermometer_eq_ignores_update_time_test() {
+        let mut updated = Thermometer::new("Bedroom".to_string(), 20.0);
+        updated.set_temperature(22.5);
+        assert!(updated.last_updated().is_some());
+        assert_eq!(updated, Thermometer::new("Bedroom".to_string(), 22.5));
+        assert_ne!(updated, Thermometer::new("Bedroom".to_string(), 23.0));
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&updated).unwrap();
+            let restored: Thermometer = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.last_updated(), None);
+            assert_eq!(restored, updated);
+        }
+    }
 }
@@ -1,14 +1,196 @@
-use crate::smart_devices::Device;
-use crate::smart_room::{AccessDevice, SmartRoom};
+use crate::frozen_home::FrozenHome;
+use crate::json;
+use crate::locale::Lang;
+use crate::smart_devices::{
+    Celsius, Device, DeviceKind, DeviceTypeError, DeviceVisitor, Outlet, OutletDevice, OutletState,
+    TemperatureSensor, Thermometer, Watt, eq_ignore_case,
+};
+use crate::smart_room::{
+    AccessDevice, AccessDeviceMut, AccessError, DuplicateKeyError, MergeStrategy, SmartRoom,
+};
 use crate::traits::Information;
+use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
+use std::fmt;
 use std::fmt::Display;
+use std::sync::mpsc::{Receiver, Sender, channel};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SmartHome {
     name: String,
     rooms: HashMap<String, SmartRoom>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    events: Option<Vec<HomeEvent>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    subscribers: Subscribers,
+}
+
+/// Channels notified about every `HomeEvent`.
+///
+/// Cloning a home does not carry its subscribers over, so receivers only
+/// observe the home they subscribed to.
+#[derive(Debug, Default)]
+struct Subscribers(Vec<Sender<HomeEvent>>);
+
+impl Clone for Subscribers {
+    fn clone(&self) -> Self {
+        Subscribers::default()
+    }
+}
+
+/// Counts of device states across a home, produced by `SmartHome::state_histogram`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StateHistogram {
+    pub outlets_on: usize,
+    pub outlets_off: usize,
+    pub thermometers: usize,
+}
+
+impl DeviceVisitor for StateHistogram {
+    fn visit_outlet(&mut self, outlet: &Outlet) {
+        match outlet.state() {
+            OutletState::On => self.outlets_on += 1,
+            OutletState::Off => self.outlets_off += 1,
+        }
+    }
+
+    fn visit_thermometer(&mut self, _thermometer: &Thermometer) {
+        self.thermometers += 1;
+    }
+}
+
+/// Aggregate values of a home captured by `SmartHome::snapshot`, without device copies.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HomeSnapshot {
+    pub room_count: usize,
+    pub device_count: usize,
+    pub total_power: Watt,
+    pub power_per_room: BTreeMap<String, Watt>,
+}
+
+/// Sends an event to the live subscribers, dropping disconnected ones, and
+/// appends it to the event log if the log is enabled.
+fn publish_event(
+    subscribers: &mut Subscribers,
+    events: &mut Option<Vec<HomeEvent>>,
+    event: HomeEvent,
+) {
+    subscribers
+        .0
+        .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    if let Some(events) = events.as_mut() {
+        events.push(event);
+    }
+}
+
+/// A view into a single room of a home, created by `SmartHome::room_entry`.
+pub struct RoomEntry<'a> {
+    entry: Entry<'a, String, SmartRoom>,
+    subscribers: &'a mut Subscribers,
+    events: &'a mut Option<Vec<HomeEvent>>,
+}
+
+impl<'a> RoomEntry<'a> {
+    /// Returns the name of the room this entry refers to
+    pub fn key(&self) -> &str {
+        self.entry.key()
+    }
+
+    /// Returns the room, inserting the one built by `default` if it is missing
+    ///
+    /// The inserted room is renamed to the entry key and a `RoomAdded` event is recorded.
+    ///
+    /// # Arguments
+    ///
+    /// * `default` - Builds the room to insert when the entry is vacant
+    pub fn or_insert_with<F: FnOnce() -> SmartRoom>(self, default: F) -> &'a mut SmartRoom {
+        match self.entry {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let mut room = default();
+                room.set_name(entry.key().clone());
+                publish_event(
+                    self.subscribers,
+                    self.events,
+                    HomeEvent::RoomAdded(entry.key().clone()),
+                );
+                entry.insert(room)
+            }
+        }
+    }
+
+    /// Applies a change to the room if it already exists
+    ///
+    /// The change is recorded as a `RoomChanged` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `modify` - A closure receiving a mutable reference to the existing room
+    pub fn and_modify<F: FnOnce(&mut SmartRoom)>(mut self, modify: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self.entry {
+            modify(entry.get_mut());
+            publish_event(
+                self.subscribers,
+                self.events,
+                HomeEvent::RoomChanged(entry.key().clone()),
+            );
+        }
+        self
+    }
+}
+
+/// Homes are equal when they have the same name and rooms; the event log
+/// and subscribers are runtime state and do not take part in the comparison.
+impl PartialEq for SmartHome {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.rooms == other.rooms
+    }
+}
+
+/// A change made to a smart home through its controlled mutation methods.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HomeEvent {
+    RoomAdded(String),
+    RoomRemoved(String),
+    /// A room was changed as a whole, e.g. through `RoomEntry::and_modify`.
+    RoomChanged(String),
+    DeviceChanged {
+        room: String,
+        device: String,
+    },
+}
+
+/// Receives the rooms and devices of a home in order, driven by `SmartHome::walk`.
+///
+/// Every method does nothing by default, so a visitor only implements the steps it needs.
+pub trait HomeVisitor {
+    fn enter_room(&mut self, _name: &str) {}
+    fn visit_device(&mut self, _room: &str, _name: &str, _device: &Device) {}
+    fn leave_room(&mut self, _name: &str) {}
+}
+
+/// A device operation addressed by room and device name, applied with `SmartHome::execute`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    TurnOn {
+        room: String,
+        device: String,
+    },
+    TurnOff {
+        room: String,
+        device: String,
+    },
+    Switch {
+        room: String,
+        device: String,
+    },
+    SetTemperature {
+        room: String,
+        device: String,
+        temperature: Celsius,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -18,27 +200,80 @@ pub struct RoomAccessError {
 
 impl Display for RoomAccessError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "RoomAccessError: {}", self.message)
+        write!(f, "smart-home error: {}", self.message)
     }
 }
 
 impl Error for RoomAccessError {}
 
+impl RoomAccessError {
+    pub(crate) fn room_not_found(key: &str, home_name: &str) -> Self {
+        RoomAccessError {
+            message: format!(
+                "Room with the name '{}' not found in the house '{}'",
+                key, home_name
+            ),
+        }
+    }
+
+    pub(crate) fn room_already_exists(key: &str, home_name: &str) -> Self {
+        RoomAccessError {
+            message: format!(
+                "Room with the name '{}' already exists in the house '{}'",
+                key, home_name
+            ),
+        }
+    }
+}
+
+/// Returned when another home cannot be imported into a smart home.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportError {
+    pub message: String,
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "smart-home error: {}", self.message)
+    }
+}
+
+impl Error for ImportError {}
+
+/// Displays the wrapped error unchanged, so every error of the crate reads
+/// `smart-home error: ...` however it is surfaced.
 #[derive(Debug, Clone, PartialEq)]
 pub enum DeviceAccessError {
     DeviceAccess(crate::smart_room::AccessError),
     RoomAccess(RoomAccessError),
+    DeviceType(DeviceTypeError),
 }
 
 impl Display for DeviceAccessError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            DeviceAccessError::DeviceAccess(e) => write!(f, "Error: {e}"),
-            DeviceAccessError::RoomAccess(e) => write!(f, "Error: {e}"),
+            DeviceAccessError::DeviceAccess(e) => write!(f, "{e}"),
+            DeviceAccessError::RoomAccess(e) => write!(f, "{e}"),
+            DeviceAccessError::DeviceType(e) => write!(f, "{e}"),
         }
     }
 }
 
+impl DeviceAccessError {
+    /// Returns `true` if the room of the lookup does not exist
+    pub fn is_room_error(&self) -> bool {
+        matches!(self, DeviceAccessError::RoomAccess(_))
+    }
+
+    /// Returns `true` if the room exists but the device is missing or of the wrong kind
+    pub fn is_device_error(&self) -> bool {
+        matches!(
+            self,
+            DeviceAccessError::DeviceAccess(_) | DeviceAccessError::DeviceType(_)
+        )
+    }
+}
+
 impl From<crate::smart_room::AccessError> for DeviceAccessError {
     fn from(error: crate::smart_room::AccessError) -> Self {
         DeviceAccessError::DeviceAccess(error)
@@ -51,26 +286,31 @@ impl From<RoomAccessError> for DeviceAccessError {
     }
 }
 
+impl From<DeviceTypeError> for DeviceAccessError {
+    fn from(error: DeviceTypeError) -> Self {
+        DeviceAccessError::DeviceType(error)
+    }
+}
+
 impl Error for DeviceAccessError {}
 
+impl From<DeviceAccessError> for std::io::Error {
+    fn from(error: DeviceAccessError) -> Self {
+        let kind = match error {
+            DeviceAccessError::DeviceType(_) => std::io::ErrorKind::InvalidInput,
+            _ => std::io::ErrorKind::NotFound,
+        };
+        std::io::Error::new(kind, error.to_string())
+    }
+}
+
 impl Information for SmartHome {
     fn name(&self) -> String {
         self.name.clone()
     }
 
     fn info(&self) -> String {
-        let sorted_rooms: BTreeMap<String, SmartRoom> = self.clone().rooms.into_iter().collect();
-        let enumerated_rooms: Vec<String> = sorted_rooms
-            .iter()
-            .enumerate()
-            .map(|(i, r)| format!("Room[{}]:{}", i, r.1.info()))
-            .collect();
-        format!(
-            "Smart Home: {}:\n Total Rooms: {}\n\n{}",
-            self.name,
-            enumerated_rooms.len(),
-            enumerated_rooms.join("\n=====================================\n")
-        )
+        self.info_localized(Lang::En)
     }
 }
 
@@ -82,7 +322,59 @@ impl SmartHome {
     /// * `name` - The name of the smart home
     /// * `rooms` - A HashMap of room names to SmartRoom instances
     pub fn new(name: String, rooms: HashMap<String, SmartRoom>) -> Self {
-        SmartHome { name, rooms }
+        SmartHome {
+            name,
+            rooms,
+            events: None,
+            subscribers: Subscribers::default(),
+        }
+    }
+
+    /// Creates a new SmartHome from `(name, room)` pairs, rejecting duplicate names
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the smart home
+    /// * `rooms` - The rooms with their identifying names
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the new SmartHome, or a DuplicateKeyError naming the first repeated room
+    pub fn try_new(
+        name: String,
+        rooms: impl IntoIterator<Item = (String, SmartRoom)>,
+    ) -> Result<Self, DuplicateKeyError> {
+        let mut unique_rooms = HashMap::new();
+        for (key, room) in rooms {
+            match unique_rooms.entry(key) {
+                Entry::Occupied(entry) => {
+                    return Err(DuplicateKeyError {
+                        message: format!(
+                            "Room name '{}' is used more than once in the house '{}'",
+                            entry.key(),
+                            name
+                        ),
+                    });
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(room);
+                }
+            }
+        }
+        Ok(SmartHome::new(name, unique_rooms))
+    }
+
+    /// Creates a new SmartHome from rooms keyed by their own names
+    ///
+    /// When several rooms share a name, the last one wins, as with `create_home!`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the smart home
+    /// * `rooms` - The rooms of the home
+    pub fn from_rooms(name: String, rooms: impl IntoIterator<Item = SmartRoom>) -> Self {
+        let rooms = rooms.into_iter().map(|room| (room.name(), room)).collect();
+        SmartHome::new(name, rooms)
     }
 
     /// Returns an immutable reference to the room with the specified name.
@@ -98,6 +390,23 @@ impl SmartHome {
         self.rooms.get(key)
     }
 
+    /// Returns an immutable reference to the room whose name matches `key` ignoring case
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The name of the room to retrieve, in any case
+    ///
+    /// # Returns
+    ///
+    /// The first matching room in name order, or None if no room matches.
+    pub fn view_room_ci(&self, key: &str) -> Option<&SmartRoom> {
+        self.rooms
+            .iter()
+            .filter(|(room_key, _)| eq_ignore_case(room_key, key))
+            .min_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, room)| room)
+    }
+
     /// Returns a mutable reference to the room with the specified name,
     /// allowing the caller to modify the room.
     ///
@@ -132,6 +441,7 @@ impl SmartHome {
     ///
     /// * `room` - The SmartRoom to add
     pub fn add_room(&mut self, room: SmartRoom) {
+        self.record_event(HomeEvent::RoomAdded(room.name()));
         self.rooms.insert(room.name().clone(), room);
     }
 
@@ -145,616 +455,3301 @@ impl SmartHome {
     ///
     /// An Option containing the removed SmartRoom if it existed, None otherwise
     pub fn remove_room(&mut self, room: &str) -> Option<SmartRoom> {
-        self.rooms.remove(room)
+        let removed = self.rooms.remove(room);
+        if removed.is_some() {
+            self.record_event(HomeEvent::RoomRemoved(room.to_string()));
+        }
+        removed
     }
-}
 
-/// A trait for accessing rooms in a smart home system
-///
-/// This trait defines the behavior for accessing rooms in a container (like SmartHome)
-/// and provides error handling for when a room cannot be found.
-pub trait AccessRoom {
-    /// Attempts to access a room by its name
+    /// Applies a change to a device in a specific room
     ///
     /// # Arguments
     ///
-    /// * `key` - The name of the room to access
+    /// * `room_name` - The name of the room containing the device
+    /// * `device_name` - The name of the device to change
+    /// * `update` - A closure receiving a mutable reference to the device
     ///
     /// # Returns
     ///
-    /// A Result containing either a reference to the SmartRoom if found,
-    /// or a RoomAccessError if the room does not exist
-    fn access_room(&self, key: &str) -> Result<&SmartRoom, RoomAccessError>;
-}
+    /// A Result that is Ok if the device was found and updated, or a DeviceAccessError
+    pub fn update_device<F: FnOnce(&mut Device)>(
+        &mut self,
+        room_name: &str,
+        device_name: &str,
+        update: F,
+    ) -> Result<(), DeviceAccessError> {
+        let device = self
+            .access_room_mut(room_name)?
+            .access_device_mut(device_name)?;
+        update(device);
+        self.record_event(HomeEvent::DeviceChanged {
+            room: room_name.to_string(),
+            device: device_name.to_string(),
+        });
+        Ok(())
+    }
 
-impl AccessRoom for SmartHome {
-    /// Implementation of `access_room` for SmartHome
+    /// Stores a new reading on a thermometer in a specific room
     ///
-    /// Attempts to retrieve a room by name and returns a Result.
-    /// If the room is not found, returns a RoomAccessError with a descriptive message.
-    fn access_room(&self, key: &str) -> Result<&SmartRoom, RoomAccessError> {
-        self.view_room(key).ok_or(RoomAccessError {
-            message: format!(
-                "Room with the name '{}' not found in the house '{}'",
-                key, self.name
-            ),
-        })
+    /// # Arguments
+    ///
+    /// * `room_name` - The name of the room containing the thermometer
+    /// * `device_name` - The name of the thermometer
+    /// * `temperature` - The new reading
+    ///
+    /// # Returns
+    ///
+    /// A Result that is Ok if the reading was stored, or a DeviceAccessError if the device
+    /// is missing or is not a thermometer
+    pub fn set_temperature(
+        &mut self,
+        room_name: &str,
+        device_name: &str,
+        temperature: Celsius,
+    ) -> Result<(), DeviceAccessError> {
+        self.access_room_mut(room_name)?
+            .access_device_mut(device_name)?
+            .try_as_thermometer_mut()?
+            .set_temperature(temperature);
+        self.record_event(HomeEvent::DeviceChanged {
+            room: room_name.to_string(),
+            device: device_name.to_string(),
+        });
+        Ok(())
     }
-}
 
-#[macro_export]
-macro_rules! create_home {
-    ($name:expr, $({ $key:expr , $value:expr }),* $(,)? ) => {{
-        let rooms = std::collections::HashMap::from(
-          [
-            $( ($key.to_string(), $value) ),*
-          ]
-        );
-        SmartHome::new($name.to_string(), rooms)
-    }};
-}
+    /// Toggles an outlet in a specific room
+    ///
+    /// # Arguments
+    ///
+    /// * `room_name` - The name of the room containing the outlet
+    /// * `device_name` - The name of the outlet
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the new state of the outlet, or a DeviceAccessError if the device
+    /// is missing or is not an outlet
+    pub fn switch(
+        &mut self,
+        room_name: &str,
+        device_name: &str,
+    ) -> Result<OutletState, DeviceAccessError> {
+        let outlet = self
+            .access_room_mut(room_name)?
+            .access_device_mut(device_name)?
+            .try_as_outlet_mut()?;
+        outlet.switch();
+        let state = outlet.state();
+        self.record_event(HomeEvent::DeviceChanged {
+            room: room_name.to_string(),
+            device: device_name.to_string(),
+        });
+        Ok(state)
+    }
 
-#[cfg(test)]
-mod tests {
-    use crate::create_room;
-    use crate::smart_devices::{Celsius, Device, OutletDevice, OutletState, Watt};
-    use crate::smart_home::{AccessRoom, DeviceAccessError, RoomAccessError, SmartHome};
-    use crate::smart_room::SmartRoom;
-    use crate::traits::Information;
-    use std::collections::HashMap;
+    /// Toggles several outlets, continuing past failures
+    ///
+    /// # Arguments
+    ///
+    /// * `targets` - Pairs of room name and outlet name, switched in order
+    ///
+    /// # Returns
+    ///
+    /// One result per target, in the same order, as returned by `switch`
+    pub fn batch_switch(
+        &mut self,
+        targets: &[(&str, &str)],
+    ) -> Vec<Result<OutletState, DeviceAccessError>> {
+        targets
+            .iter()
+            .map(|(room_name, device_name)| self.switch(room_name, device_name))
+            .collect()
+    }
 
-    #[test]
-    fn view_home_rooms() {
-        let room1 = SmartRoom::new("Living Room".to_string(), HashMap::new());
-        let room2 = SmartRoom::new("Bedroom".to_string(), HashMap::new());
-        let home = SmartHome::new(
-            "My Home".to_string(),
-            HashMap::from([
-                ("Living Room".to_string(), room1),
-                ("Bedroom".to_string(), room2),
-            ]),
-        );
+    /// Applies a single command to the home
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command to apply
+    ///
+    /// # Returns
+    ///
+    /// A Result that is Ok if the command was applied, or a DeviceAccessError if the device
+    /// is missing or is of the wrong kind for the command
+    pub fn execute(&mut self, command: &Command) -> Result<(), DeviceAccessError> {
+        match command {
+            Command::TurnOn { room, device } | Command::TurnOff { room, device } => {
+                let outlet = self
+                    .access_room_mut(room)?
+                    .access_device_mut(device)?
+                    .try_as_outlet_mut()?;
+                if matches!(command, Command::TurnOn { .. }) {
+                    outlet.turn_on();
+                } else {
+                    outlet.turn_off();
+                }
+                self.record_event(HomeEvent::DeviceChanged {
+                    room: room.clone(),
+                    device: device.clone(),
+                });
+                Ok(())
+            }
+            Command::Switch { room, device } => self.switch(room, device).map(|_| ()),
+            Command::SetTemperature {
+                room,
+                device,
+                temperature,
+            } => self.set_temperature(room, device, *temperature),
+        }
+    }
 
-        assert_eq!(home.view_room("Living Room").unwrap().name(), "Living Room");
-        assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
+    /// Applies timestamped commands in timestamp order
+    ///
+    /// Commands with equal timestamps keep their order in the slice. Replay stops at the
+    /// first failing command; the commands applied before it stay applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - `(timestamp, command)` pairs in any order
+    ///
+    /// # Returns
+    ///
+    /// A Result that is Ok if every command was applied, or the DeviceAccessError of the
+    /// first failing command
+    pub fn replay(&mut self, events: &[(u64, Command)]) -> Result<(), DeviceAccessError> {
+        let mut ordered: Vec<&(u64, Command)> = events.iter().collect();
+        ordered.sort_by_key(|(timestamp, _)| *timestamp);
+        for (_, command) in ordered {
+            self.execute(command)?;
+        }
+        Ok(())
     }
 
-    #[test]
-    fn create_home_test() {
-        {
-            let home = create_home!(
-                "My Smart Home",
-                {"Living Room",
-                SmartRoom::new("Living Room".to_string(), HashMap::new())},
-                {"Bedroom",
-                SmartRoom::new("Bedroom".to_string(), HashMap::new())},
+    /// Starts recording every mutation made through `SmartHome` methods
+    ///
+    /// The log receives the same events as subscribers. Changes made through references
+    /// handed out by `get_room` or `access_room_mut` are not recorded.
+    ///
+    /// Recording is off by default so that long-running homes do not grow an unbounded log.
+    /// Calling this again keeps the events recorded so far.
+    pub fn enable_event_log(&mut self) {
+        self.events.get_or_insert_with(Vec::new);
+    }
 
-            );
+    /// Returns the recorded events in the order they happened
+    ///
+    /// # Returns
+    ///
+    /// A slice of recorded events, empty if the event log was never enabled
+    pub fn events(&self) -> &[HomeEvent] {
+        self.events.as_deref().unwrap_or(&[])
+    }
 
-            assert_eq!(home.name(), "My Smart Home");
-            assert_eq!(home.view_room("Living Room").unwrap().name(), "Living Room");
-            assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
-        }
-        {
-            let home = create_home!(
-                "My Smart Home",
-                {
-                    "Bedroom",
-                    create_room!(
-                        "Bedroom",
-                        "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
-                        "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
-                        "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius))
-                },
-            );
+    /// Subscribes to the events produced by the mutation methods of the home
+    ///
+    /// Changes made through references handed out by `get_room` or `access_room_mut`
+    /// are not observed.
+    ///
+    /// Events are delivered regardless of whether the event log is enabled.
+    /// Dropping the receiver unsubscribes it on the next event.
+    ///
+    /// # Returns
+    ///
+    /// A Receiver getting every subsequent HomeEvent of this home
+    pub fn subscribe(&mut self) -> Receiver<HomeEvent> {
+        let (sender, receiver) = channel();
+        self.subscribers.0.push(sender);
+        receiver
+    }
 
-            assert_eq!(home.name(), "My Smart Home");
-            assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
-            assert!(home.view_room("Living Room").is_none());
+    fn record_event(&mut self, event: HomeEvent) {
+        publish_event(&mut self.subscribers, &mut self.events, event);
+    }
+
+    /// Gets the entry for a room for in-place find-or-create manipulation
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the room
+    ///
+    /// # Returns
+    ///
+    /// A RoomEntry that resolves the room with a single lookup
+    pub fn room_entry(&mut self, name: String) -> RoomEntry<'_> {
+        RoomEntry {
+            entry: self.rooms.entry(name),
+            subscribers: &mut self.subscribers,
+            events: &mut self.events,
         }
     }
 
+    /// Returns an iterator over every device in the home together with its room
+    ///
+    /// Devices are ordered by room name first and then by device name.
+    ///
+    /// # Returns
+    ///
+    /// An iterator yielding `(room_name, device_name, device)` tuples
+    pub fn iter_devices(&self) -> impl Iterator<Item = (&str, &str, &Device)> {
+        let sorted_rooms: BTreeMap<&str, &SmartRoom> =
+            self.rooms.iter().map(|(k, r)| (k.as_str(), r)).collect();
+        sorted_rooms.into_iter().flat_map(|(room_name, room)| {
+            room.iter_devices()
+                .map(move |(device_name, device)| (room_name, device_name, device))
+        })
+    }
+
+    /// Walks the home with a visitor, room by room
+    ///
+    /// Rooms are visited in name order and the devices of each room in name order
+    /// between its `enter_room` and `leave_room` calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `visitor` - The visitor receiving the hierarchy
+    pub fn walk<V: HomeVisitor>(&self, visitor: &mut V) {
+        let sorted_rooms: BTreeMap<&String, &SmartRoom> = self.rooms.iter().collect();
+        for (room_name, room) in sorted_rooms {
+            visitor.enter_room(room_name);
+            for (device_name, device) in room.iter_devices() {
+                visitor.visit_device(room_name, device_name, device);
+            }
+            visitor.leave_room(room_name);
+        }
+    }
+
+    /// Folds the rooms of the home into a single value, in name order
+    ///
+    /// # Arguments
+    ///
+    /// * `init` - The initial accumulator
+    /// * `f` - Combines the accumulator with the next room
+    ///
+    /// # Returns
+    ///
+    /// The final accumulator
+    pub fn fold_rooms<B, F: FnMut(B, &SmartRoom) -> B>(&self, init: B, f: F) -> B {
+        let sorted_rooms: BTreeMap<&String, &SmartRoom> = self.rooms.iter().collect();
+        sorted_rooms.into_values().fold(init, f)
+    }
+
+    /// Returns the combined current power draw of every room in the home
+    ///
+    /// The sum saturates at `Watt::MAX` instead of overflowing.
+    pub fn total_power_usage(&self) -> Watt {
+        self.rooms.values().fold(0, |total: Watt, room| {
+            total.saturating_add(room.total_power_usage())
+        })
+    }
+
+    /// Returns the combined rated power of every outlet in the home, whether on or off
+    ///
+    /// The sum saturates at `Watt::MAX` instead of overflowing.
+    pub fn total_power_rating(&self) -> Watt {
+        self.rooms.values().fold(0, |total: Watt, room| {
+            total.saturating_add(room.total_power_rating())
+        })
+    }
+
+    /// Returns the current draw as a share of the rated capacity of all outlets
+    ///
+    /// # Returns
+    ///
+    /// `total_power_usage / total_power_rating` clamped to `0.0..=1.0`, or 0.0 if the home
+    /// has no rated outlets
+    pub fn load_factor(&self) -> f64 {
+        let rating = self.total_power_rating();
+        if rating == 0 {
+            return 0.0;
+        }
+        (self.total_power_usage() as f64 / rating as f64).clamp(0.0, 1.0)
+    }
+
+    /// Returns each room's share of the current power draw of the home
+    ///
+    /// # Returns
+    ///
+    /// Pairs of room name and share, sorted by name. The shares sum to 1.0, or are all 0.0
+    /// if the home draws no power
+    pub fn power_distribution(&self) -> Vec<(String, f64)> {
+        let rooms: BTreeMap<&String, Watt> = self
+            .rooms
+            .iter()
+            .map(|(name, room)| (name, room.total_power_usage()))
+            .collect();
+        let total: u128 = rooms.values().map(|power| *power as u128).sum();
+        rooms
+            .into_iter()
+            .map(|(name, power)| {
+                let share = if total == 0 {
+                    0.0
+                } else {
+                    power as f64 / total as f64
+                };
+                (name.clone(), share)
+            })
+            .collect()
+    }
+
+    /// Exports the home -> rooms -> devices hierarchy as a Graphviz DOT graph
+    ///
+    /// Rooms and devices are emitted in sorted order so the output is stable.
+    /// Node identifiers are built from the room/device path, so equal names
+    /// in different rooms do not collide.
+    ///
+    /// # Returns
+    ///
+    /// A String containing the DOT description of the home
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec![
+            format!("digraph \"{}\" {{", escape_dot(&self.name)),
+            format!("    \"home\" [label=\"{}\"];", escape_dot(&self.name)),
+        ];
+        let sorted_rooms: BTreeMap<&String, &SmartRoom> = self.rooms.iter().collect();
+        for (room_name, room) in sorted_rooms {
+            let room_id = escape_dot(&format!("room:{}", room_name));
+            lines.push(format!(
+                "    \"{}\" [label=\"{}\"];",
+                room_id,
+                escape_dot(room_name)
+            ));
+            lines.push(format!("    \"home\" -> \"{}\";", room_id));
+            for (device_name, _) in room.iter_devices() {
+                let device_id = escape_dot(&format!("device:{}/{}", room_name, device_name));
+                lines.push(format!(
+                    "    \"{}\" [label=\"{}\"];",
+                    device_id,
+                    escape_dot(device_name)
+                ));
+                lines.push(format!("    \"{}\" -> \"{}\";", room_id, device_id));
+            }
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    /// Exports the home as a Markdown document with one device table per room
+    ///
+    /// Rooms and devices are emitted in sorted order so the output is stable.
+    /// Outlets show their state and current power, thermometers their temperature.
+    ///
+    /// # Returns
+    ///
+    /// A String containing the Markdown document
+    pub fn to_markdown(&self) -> String {
+        let mut lines = vec![format!("# {}", escape_markdown(&self.name))];
+        let sorted_rooms: BTreeMap<&String, &SmartRoom> = self.rooms.iter().collect();
+        for (room_name, room) in sorted_rooms {
+            lines.push(String::new());
+            lines.push(format!("## {}", escape_markdown(room_name)));
+            lines.push(String::new());
+            lines.push("| Device | Kind | State | Power |".to_string());
+            lines.push("| --- | --- | --- | --- |".to_string());
+            for (device_name, device) in room.iter_devices() {
+                let (state, power) = match device {
+                    Device::OutletType(outlet) => (
+                        outlet.state().to_string(),
+                        format!("{} W", outlet.power_usage()),
+                    ),
+                    Device::ThermometerType(thermometer) => (
+                        format!("{:.1}°C", thermometer.current_temperature()),
+                        "-".to_string(),
+                    ),
+                    Device::Empty => ("-".to_string(), "-".to_string()),
+                };
+                lines.push(format!(
+                    "| {} | {} | {} | {} |",
+                    escape_markdown(device_name),
+                    device.kind(),
+                    state,
+                    power
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Returns the home report with labels in the given language
+    ///
+    /// Numbers and temperatures are formatted the same way in every language.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The language of the report labels
+    ///
+    /// # Returns
+    ///
+    /// The same report as `info()`, with every label translated
+    pub fn info_localized(&self, lang: Lang) -> String {
+        let mut info = String::new();
+        self.write_info_localized(&mut info, lang)
+            .expect("writing to a String cannot fail");
+        info
+    }
+
+    /// Writes the home report into any `fmt::Write` sink
+    ///
+    /// Rooms are written one after another, so the full report is never built in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `w` - The sink receiving the same text as `info()`
+    pub fn write_info<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        self.write_info_localized(w, Lang::En)
+    }
+
+    /// Writes the home report with labels in the given language into a `fmt::Write` sink
+    ///
+    /// # Arguments
+    ///
+    /// * `w` - The sink receiving the same text as `info_localized(lang)`
+    /// * `lang` - The language of the report labels
+    pub fn write_info_localized<W: fmt::Write>(&self, w: &mut W, lang: Lang) -> fmt::Result {
+        let labels = lang.labels();
+        write!(
+            w,
+            "{}: {}:\n {}: {}\n\n",
+            labels.smart_home,
+            self.name,
+            labels.total_rooms,
+            self.rooms.len()
+        )?;
+        for (i, room) in self.rooms_sorted().into_iter().enumerate() {
+            if i > 0 {
+                w.write_str("\n=====================================\n")?;
+            }
+            write!(w, "{}[{}]:", labels.room, i)?;
+            room.write_info_localized(w, lang)?;
+        }
+        Ok(())
+    }
+
+    /// Captures the aggregate values of the home as owned data
+    ///
+    /// Only names and numbers are copied, which keeps the snapshot much cheaper than `clone`.
+    ///
+    /// # Returns
+    ///
+    /// A HomeSnapshot with the room and device counts and the total and per-room power draw
+    pub fn snapshot(&self) -> HomeSnapshot {
+        let power_per_room: BTreeMap<String, Watt> = self
+            .rooms
+            .iter()
+            .map(|(name, room)| (name.clone(), room.total_power_usage()))
+            .collect();
+        HomeSnapshot {
+            room_count: self.rooms.len(),
+            device_count: self.rooms.values().map(SmartRoom::len).sum(),
+            total_power: power_per_room
+                .values()
+                .fold(0, |total: Watt, power| total.saturating_add(*power)),
+            power_per_room,
+        }
+    }
+
+    /// Tallies the outlet states and thermometers of the whole home in a single pass
+    ///
+    /// # Returns
+    ///
+    /// A StateHistogram with the number of outlets on, outlets off and thermometers
+    pub fn state_histogram(&self) -> StateHistogram {
+        let mut histogram = StateHistogram::default();
+        for (_, _, device) in self.iter_devices() {
+            device.accept(&mut histogram);
+        }
+        histogram
+    }
+
+    /// Lists every outlet in the home that is in the given state
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The outlet state to look for
+    ///
+    /// # Returns
+    ///
+    /// A Vec of `(room_name, device_name)` pairs sorted by room and then device name
+    pub fn outlets_in_state(&self, state: OutletState) -> Vec<(String, String)> {
+        self.iter_devices()
+            .filter(|(_, _, device)| {
+                matches!(device, Device::OutletType(outlet) if outlet.state() == state)
+            })
+            .map(|(room_name, device_name, _)| (room_name.to_string(), device_name.to_string()))
+            .collect()
+    }
+
+    /// Finds the switched-on outlet with the highest current power draw
+    ///
+    /// Ties are resolved in favour of the first room and device in name order.
+    ///
+    /// # Returns
+    ///
+    /// An Option containing `(room_name, device_name, power_usage)`, or None if no outlet is on
+    pub fn largest_consumer(&self) -> Option<(String, String, Watt)> {
+        let mut largest: Option<(&str, &str, Watt)> = None;
+        for (room_name, device_name, device) in self.iter_devices() {
+            let outlet = match device {
+                Device::OutletType(outlet) if outlet.state() == OutletState::On => outlet,
+                _ => continue,
+            };
+            let usage = outlet.power_usage();
+            if largest.is_none_or(|(_, _, max)| usage > max) {
+                largest = Some((room_name, device_name, usage));
+            }
+        }
+        largest.map(|(room, device, usage)| (room.to_string(), device.to_string(), usage))
+    }
+
+    /// Changes the name of the smart home
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The new name of the smart home
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    /// Renames a room, keeping its key in the home in sync with its name
+    ///
+    /// The rename is recorded as a `RoomRemoved` event for the old name followed by a
+    /// `RoomAdded` event for the new one. Renaming a room to its own name records nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The current name of the room
+    /// * `new_name` - The new name of the room
+    ///
+    /// # Returns
+    ///
+    /// A Result that is Ok if the room was renamed, or a RoomAccessError if the room
+    /// does not exist or the new name is already taken by another room
+    pub fn rename_room(&mut self, key: &str, new_name: String) -> Result<(), RoomAccessError> {
+        if key != new_name && self.rooms.contains_key(&new_name) {
+            return Err(RoomAccessError::room_already_exists(&new_name, &self.name));
+        }
+        let mut room = self
+            .rooms
+            .remove(key)
+            .ok_or_else(|| RoomAccessError::room_not_found(key, &self.name))?;
+        room.set_name(new_name.clone());
+        self.rooms.insert(new_name.clone(), room);
+        if key != new_name {
+            self.record_event(HomeEvent::RoomRemoved(key.to_string()));
+            self.record_event(HomeEvent::RoomAdded(new_name));
+        }
+        Ok(())
+    }
+
+    /// Adds a deep copy of a room under a new name
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The name of the room to copy
+    /// * `new_name` - The name of the copy
+    ///
+    /// # Returns
+    ///
+    /// A Result that is Ok if the copy was added, or a RoomAccessError if the source room
+    /// does not exist or the new name is already taken
+    pub fn clone_room(&mut self, source: &str, new_name: &str) -> Result<(), RoomAccessError> {
+        if self.rooms.contains_key(new_name) {
+            return Err(RoomAccessError::room_already_exists(new_name, &self.name));
+        }
+        let mut room = self.access_room(source)?.clone();
+        room.set_name(new_name.to_string());
+        self.add_room(room);
+        Ok(())
+    }
+
+    /// Flattens the home into a single map of devices keyed by their path
+    ///
+    /// # Returns
+    ///
+    /// A BTreeMap from `"room/device"` paths to references to the devices
+    pub fn flatten(&self) -> BTreeMap<String, &Device> {
+        self.iter_devices()
+            .map(|(room_name, device_name, device)| {
+                (format!("{}/{}", room_name, device_name), device)
+            })
+            .collect()
+    }
+
+    /// Counts the devices in the home grouped by their kind
+    ///
+    /// # Returns
+    ///
+    /// A BTreeMap from each device kind present in the home to the number of such devices
+    pub fn group_by_kind(&self) -> BTreeMap<DeviceKind, usize> {
+        let mut groups = BTreeMap::new();
+        for (_, _, device) in self.iter_devices() {
+            *groups.entry(device.kind()).or_insert(0) += 1;
+        }
+        groups
+    }
+
+    /// Checks that the home, its rooms and their devices are consistently named
+    ///
+    /// Every room must be stored under its own name, and each room is validated
+    /// with `SmartRoom::validate`. No names may be empty.
+    ///
+    /// # Returns
+    ///
+    /// A Result that is Ok if no problems were found, or Err with a description of every problem
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+        if self.name.is_empty() {
+            problems.push("Home has an empty name".to_string());
+        }
+        let sorted_rooms: BTreeMap<&String, &SmartRoom> = self.rooms.iter().collect();
+        for (key, room) in sorted_rooms {
+            let name = room.name();
+            if !name.is_empty() && &name != key {
+                problems.push(format!(
+                    "Room '{}' is stored under the key '{}' in the house '{}'",
+                    name, key, self.name
+                ));
+            }
+            if let Err(room_problems) = room.validate() {
+                problems.extend(room_problems);
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Serializes the home to JSON without using any external crate
+    ///
+    /// Rooms and devices are written in name order, so the output is stable.
+    /// Devices are tagged with a lowercase `kind` field (`outlet`, `thermometer` or `empty`).
+    ///
+    /// # Returns
+    ///
+    /// A String with a JSON object holding the home `name` and its `rooms` by name
+    pub fn to_json_manual(&self) -> String {
+        let mut out = String::from("{");
+        json::write_key(&mut out, "name");
+        json::write_string(&mut out, &self.name);
+        out.push(',');
+        json::write_key(&mut out, "rooms");
+        out.push('{');
+        let sorted_rooms: BTreeMap<&String, &SmartRoom> = self.rooms.iter().collect();
+        for (i, (key, room)) in sorted_rooms.into_iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            json::write_key(&mut out, key);
+            room.write_json(&mut out);
+        }
+        out.push_str("}}");
+        out
+    }
+
+    /// Ranks the rooms by their current power draw
+    ///
+    /// # Returns
+    ///
+    /// A Vec of `(room_name, power_usage)` pairs sorted by descending power usage,
+    /// with ties ordered by room name. Rooms drawing nothing are included with 0.
+    pub fn power_per_room(&self) -> Vec<(String, Watt)> {
+        let mut ranking: Vec<(String, Watt)> = self
+            .rooms
+            .iter()
+            .map(|(name, room)| (name.clone(), room.total_power_usage()))
+            .collect();
+        ranking.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranking
+    }
+
+    /// Renders the current power draw of each room as an ASCII bar chart
+    ///
+    /// Rooms are sorted by name, one per line. The room with the highest draw gets a bar
+    /// of `width` characters and the others are scaled relative to it, rounded to the
+    /// nearest character. Rooms drawing nothing render an empty bar.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The length of the longest bar
+    ///
+    /// # Returns
+    ///
+    /// Lines of the form `"Bedroom     |#####     | 250W"`
+    pub fn power_bar_chart(&self, width: usize) -> String {
+        let rooms: BTreeMap<&String, Watt> = self
+            .rooms
+            .iter()
+            .map(|(name, room)| (name, room.total_power_usage()))
+            .collect();
+        let max_power = rooms.values().copied().max().unwrap_or(0);
+        let label_width = rooms
+            .keys()
+            .map(|name| name.chars().count())
+            .max()
+            .unwrap_or(0);
+        rooms
+            .iter()
+            .map(|(name, power)| {
+                let bar_length = if max_power == 0 {
+                    0
+                } else {
+                    ((*power as u128 * width as u128 + max_power as u128 / 2) / max_power as u128)
+                        as usize
+                };
+                format!(
+                    "{:<label_width$} |{:<width$}| {}W",
+                    name,
+                    "#".repeat(bar_length),
+                    power
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Lists the rooms drawing more power than a threshold
+    ///
+    /// # Arguments
+    ///
+    /// * `watts` - The threshold; rooms drawing exactly this much are not included
+    ///
+    /// # Returns
+    ///
+    /// The names of the matching rooms, sorted
+    pub fn rooms_over(&self, watts: Watt) -> Vec<&str> {
+        let mut rooms: Vec<&str> = self
+            .rooms
+            .iter()
+            .filter(|(_, room)| room.total_power_usage() > watts)
+            .map(|(name, _)| name.as_str())
+            .collect();
+        rooms.sort();
+        rooms
+    }
+
+    /// Checks the current draw of each room against its power budget
+    ///
+    /// # Arguments
+    ///
+    /// * `budgets` - The allowed draw per room name; rooms without an entry are skipped
+    ///
+    /// # Returns
+    ///
+    /// `(room, usage, budget)` for each room drawing more than its budget, sorted by room name
+    pub fn validate_power_budgets(
+        &self,
+        budgets: &HashMap<String, Watt>,
+    ) -> Vec<(String, Watt, Watt)> {
+        let sorted_rooms: BTreeMap<&String, &SmartRoom> = self.rooms.iter().collect();
+        sorted_rooms
+            .into_iter()
+            .filter_map(|(name, room)| {
+                let budget = *budgets.get(name)?;
+                let usage = room.total_power_usage();
+                (usage > budget).then(|| (name.clone(), usage, budget))
+            })
+            .collect()
+    }
+
+    /// Returns the combined current power draw of the home, detecting overflow
+    ///
+    /// Unlike `total_power_usage`, which saturates, this reports an overflow explicitly.
+    ///
+    /// # Returns
+    ///
+    /// An Option containing the total power usage, or None if the sum overflows `Watt`
+    pub fn checked_total_power(&self) -> Option<Watt> {
+        self.iter_devices()
+            .try_fold(0 as Watt, |total, (_, _, device)| {
+                total.checked_add(device.power_usage())
+            })
+    }
+
+    /// Returns the cost of running the home at its current power draw
+    ///
+    /// # Arguments
+    ///
+    /// * `price_per_kwh` - The energy price per kilowatt-hour
+    /// * `hours` - How long the current draw is sustained
+    ///
+    /// # Returns
+    ///
+    /// The unrounded cost in the currency of `price_per_kwh`
+    pub fn energy_cost(&self, price_per_kwh: f64, hours: f64) -> f64 {
+        // Dividing last keeps results such as 525 / 1000 exact to the printed digits.
+        self.total_power_usage() as f64 * hours * price_per_kwh / 1000.0
+    }
+
+    /// Returns the cost of running the home at its current power draw, rounded for billing
+    ///
+    /// Rounding is half-up: a cost exactly halfway between two steps goes to the larger one,
+    /// so `0.305` at 2 decimals becomes `0.31`. The cost is rounded as it prints, not by its
+    /// exact binary value, so a cost printing as `0.3049999` becomes `0.3`. With 16 or more
+    /// decimals the cost is returned unrounded.
+    ///
+    /// # Arguments
+    ///
+    /// * `price_per_kwh` - The energy price per kilowatt-hour
+    /// * `hours` - How long the current draw is sustained
+    /// * `decimals` - The number of decimal places to keep, e.g. 2 for cents
+    pub fn energy_cost_rounded(&self, price_per_kwh: f64, hours: f64, decimals: u32) -> f64 {
+        round_half_up(self.energy_cost(price_per_kwh, hours), decimals)
+    }
+
+    /// Returns how much more power this home draws than another home state
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The home state to compare against, usually an earlier snapshot
+    ///
+    /// # Returns
+    ///
+    /// The signed difference `self - other` in watts, clamped to the `i64` range
+    pub fn power_delta(&self, other: &SmartHome) -> i64 {
+        let delta = self.total_power_usage() as i128 - other.total_power_usage() as i128;
+        delta.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    /// Shifts the reading of every thermometer in the home by the given delta
+    ///
+    /// Outlets are left untouched. The offset is a calibration rather than a new reading,
+    /// so the thermometers keep their update time. Every shifted thermometer is recorded as
+    /// a `DeviceChanged` event, in room and device name order.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The offset added to each thermometer reading
+    pub fn offset_all_thermometers(&mut self, delta: Celsius) {
+        let thermometers: Vec<(String, String)> = self
+            .iter_devices()
+            .filter(|(_, _, device)| matches!(device, Device::ThermometerType(_)))
+            .map(|(room, name, _)| (room.to_string(), name.to_string()))
+            .collect();
+        for (room, device) in thermometers {
+            if let Some(Device::ThermometerType(thermometer)) = self
+                .rooms
+                .get_mut(&room)
+                .and_then(|smart_room| smart_room.get_device(&device))
+            {
+                thermometer.offset_temperature(delta);
+            }
+            self.record_event(HomeEvent::DeviceChanged { room, device });
+        }
+    }
+
+    /// Returns references to all rooms sorted by name
+    ///
+    /// # Returns
+    ///
+    /// A Vec of room references in the same order as they appear in `info()`
+    pub fn rooms_sorted(&self) -> Vec<&SmartRoom> {
+        let sorted_rooms: BTreeMap<&String, &SmartRoom> = self.rooms.iter().collect();
+        sorted_rooms.into_values().collect()
+    }
+
+    /// Removes a device from a specific room
+    ///
+    /// A successful removal is recorded as a `DeviceChanged` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `room_name` - The name of the room containing the device
+    /// * `device_name` - The name of the device to remove
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the removed device, or a DeviceAccessError if the room or device is missing
+    pub fn remove_device(
+        &mut self,
+        room_name: &str,
+        device_name: &str,
+    ) -> Result<Device, DeviceAccessError> {
+        let room = self.access_room_mut(room_name)?;
+        let device = room
+            .remove_device(device_name)
+            .ok_or_else(|| AccessError::device_not_found(device_name, &room.name()))?;
+        self.record_event(HomeEvent::DeviceChanged {
+            room: room_name.to_string(),
+            device: device_name.to_string(),
+        });
+        Ok(device)
+    }
+
+    /// Adds a device to an existing room
+    ///
+    /// A missing room is reported as an error rather than created.
+    /// A device already stored under the key is replaced. The change is recorded as a
+    /// `DeviceChanged` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `room_name` - The name of the room to add the device to
+    /// * `key` - The unique identifier for the device in the room
+    /// * `device` - The device to add
+    ///
+    /// # Returns
+    ///
+    /// A Result that is Ok if the device was added, or a RoomAccessError if the room does not exist
+    pub fn add_device(
+        &mut self,
+        room_name: &str,
+        key: String,
+        device: Device,
+    ) -> Result<(), RoomAccessError> {
+        self.access_room_mut(room_name)?
+            .add_device(key.clone(), device);
+        self.record_event(HomeEvent::DeviceChanged {
+            room: room_name.to_string(),
+            device: key,
+        });
+        Ok(())
+    }
+
+    /// Moves all rooms of another home into this one
+    ///
+    /// Rooms that exist in both homes are merged device by device with
+    /// `SmartRoom::merge_from`, so the strategy only decides device key collisions.
+    ///
+    /// Rooms are merged in name order. Each new room is recorded as a `RoomAdded` event and
+    /// each device inserted into an existing room as a `DeviceChanged` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The home whose rooms are moved; its name is discarded
+    /// * `strategy` - How to resolve devices stored under the same key in the same room
+    pub fn merge_from(&mut self, other: SmartHome, strategy: MergeStrategy) {
+        let sorted_rooms: BTreeMap<String, SmartRoom> = other.rooms.into_iter().collect();
+        for (key, room) in sorted_rooms {
+            match self.rooms.entry(key.clone()) {
+                Entry::Occupied(mut entry) => {
+                    let changed: Vec<String> = room
+                        .iter_devices()
+                        .filter(|(device, _)| {
+                            strategy == MergeStrategy::Overwrite
+                                || entry.get().view_device(device).is_none()
+                        })
+                        .map(|(device, _)| device.to_string())
+                        .collect();
+                    entry.get_mut().merge_from(room, strategy);
+                    for device in changed {
+                        self.record_event(HomeEvent::DeviceChanged {
+                            room: key.clone(),
+                            device,
+                        });
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(room);
+                    self.record_event(HomeEvent::RoomAdded(key));
+                }
+            }
+        }
+    }
+
+    /// Parses a home from JSON and merges it into this one with `merge_from`
+    ///
+    /// Only available with the `serde` feature. The JSON uses the same format as
+    /// `to_json_manual`. Events are recorded as described for `merge_from`.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The serialized home to import; its name is discarded
+    /// * `strategy` - How to resolve devices stored under the same key in the same room
+    ///
+    /// # Returns
+    ///
+    /// A Result that is Ok if the home was imported, or an ImportError if the JSON is invalid.
+    /// Nothing is merged when parsing fails.
+    #[cfg(feature = "serde")]
+    pub fn import_merge_from_json(
+        &mut self,
+        json: &str,
+        strategy: MergeStrategy,
+    ) -> Result<(), ImportError> {
+        let other: SmartHome = serde_json::from_str(json).map_err(|error| ImportError {
+            message: format!("Invalid home JSON: {}", error),
+        })?;
+        self.merge_from(other, strategy);
+        Ok(())
+    }
+
+    /// Removes every room that has no devices
+    ///
+    /// Rooms are removed through `remove_room`, so the removals are recorded as events.
+    ///
+    /// # Returns
+    ///
+    /// The names of the removed rooms, sorted
+    pub fn prune_empty_rooms(&mut self) -> Vec<String> {
+        let mut empty_rooms: Vec<String> = self
+            .rooms
+            .iter()
+            .filter(|(_, room)| room.is_empty())
+            .map(|(name, _)| name.clone())
+            .collect();
+        empty_rooms.sort();
+        for name in &empty_rooms {
+            self.remove_room(name);
+        }
+        empty_rooms
+    }
+
+    /// Returns an independent deep copy of the home
+    ///
+    /// Rooms and devices are copied, so changes to the fork never affect the original and
+    /// vice versa. Like `clone`, the recorded events are copied but subscribers are not.
+    ///
+    /// # Returns
+    ///
+    /// A new SmartHome equal to this one
+    #[must_use]
+    pub fn fork(&self) -> SmartHome {
+        self.clone()
+    }
+
+    /// Converts the home into an immutable form optimized for lookups
+    ///
+    /// Rooms and devices are moved into name-sorted vectors searched with binary search.
+    /// The event log and subscribers are dropped.
+    ///
+    /// # Returns
+    ///
+    /// A FrozenHome exposing the read API of the home
+    pub fn freeze(self) -> FrozenHome {
+        FrozenHome::new(self.name, self.rooms)
+    }
+
+    /// Returns the number of rooms the home can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.rooms.capacity()
+    }
+
+    /// Releases the memory the home and each of its rooms kept after removals
+    pub fn shrink_to_fit(&mut self) {
+        self.rooms.shrink_to_fit();
+        for room in self.rooms.values_mut() {
+            room.shrink_to_fit();
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a SmartHome {
+    type Item = &'a SmartRoom;
+    type IntoIter = std::vec::IntoIter<&'a SmartRoom>;
+
+    /// Iterates over the rooms of the home sorted by name
+    fn into_iter(self) -> Self::IntoIter {
+        self.rooms_sorted().into_iter()
+    }
+}
+
+/// Rounding to this many decimal places or more returns the value unchanged, as an `f64`
+/// carries at most 17 significant digits
+const MAX_ROUNDING_DECIMALS: u32 = 16;
+
+/// Rounds half-up to the given number of decimal places
+///
+/// Rounding works on the shortest decimal representation of the value, the one `{}`
+/// prints, rather than on its exact binary value. `0.305` is stored as
+/// `0.30499999999999999334`, yet prints as `0.305` and rounds to `0.31`, while a value that
+/// prints as `0.3049999` rounds to `0.3`. Halfway cases round away from zero. Non-finite
+/// values and `decimals` of `MAX_ROUNDING_DECIMALS` or more return the value unchanged.
+fn round_half_up(value: f64, decimals: u32) -> f64 {
+    if !value.is_finite() || decimals >= MAX_ROUNDING_DECIMALS {
+        return value;
+    }
+    // Scientific notation yields the shortest round-trip digits, e.g. "3.05e-1".
+    let repr = format!("{:e}", value.abs());
+    let Some((mantissa, exponent)) = repr.split_once('e') else {
+        return value;
+    };
+    let Ok(exponent) = exponent.parse::<i32>() else {
+        return value;
+    };
+    let digits: Vec<u64> = mantissa
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(u64::from)
+        .collect();
+    // Number of leading digits at or before the last kept decimal place.
+    let kept = exponent + 1 + decimals as i32;
+    if kept >= digits.len() as i32 {
+        return value;
+    }
+    let (scaled, next_digit) = if kept < 0 {
+        (0, 0)
+    } else {
+        let kept = kept as usize;
+        let scaled = digits[..kept]
+            .iter()
+            .fold(0u64, |number, digit| number * 10 + digit);
+        (scaled, digits[kept])
+    };
+    let rounded = scaled + u64::from(next_digit >= 5);
+    (rounded as f64 / 10f64.powi(decimals as i32)).copysign(value)
+}
+
+/// Escapes the characters that would break a Markdown heading or table cell
+fn escape_markdown(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+/// Escapes a string for use inside a double-quoted DOT identifier
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// A trait for accessing rooms in a smart home system
+///
+/// This trait defines the behavior for accessing rooms in a container (like SmartHome)
+/// and provides error handling for when a room cannot be found.
+pub trait AccessRoom {
+    /// Attempts to access a room by its name
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The name of the room to access
+    ///
+    /// # Returns
+    ///
+    /// A Result containing either a reference to the SmartRoom if found,
+    /// or a RoomAccessError if the room does not exist
+    fn access_room(&self, key: &str) -> Result<&SmartRoom, RoomAccessError>;
+}
+
+impl AccessRoom for SmartHome {
+    /// Implementation of `access_room` for SmartHome
+    ///
+    /// Attempts to retrieve a room by name and returns a Result.
+    /// If the room is not found, returns a RoomAccessError with a descriptive message.
+    fn access_room(&self, key: &str) -> Result<&SmartRoom, RoomAccessError> {
+        self.view_room(key)
+            .ok_or_else(|| RoomAccessError::room_not_found(key, &self.name))
+    }
+}
+
+/// A trait for mutably accessing rooms in a smart home system
+///
+/// This is the mutable counterpart of `AccessRoom`, reporting a missing room
+/// with the same RoomAccessError instead of a bare Option.
+pub trait AccessRoomMut {
+    /// Attempts to access a room by its name for modification
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The name of the room to access
+    ///
+    /// # Returns
+    ///
+    /// A Result containing either a mutable reference to the SmartRoom if found,
+    /// or a RoomAccessError if the room does not exist
+    fn access_room_mut(&mut self, key: &str) -> Result<&mut SmartRoom, RoomAccessError>;
+}
+
+impl AccessRoomMut for SmartHome {
+    /// Implementation of `access_room_mut` for SmartHome
+    ///
+    /// Attempts to retrieve a room by name for modification and returns a Result.
+    /// If the room is not found, returns a RoomAccessError with a descriptive message.
+    fn access_room_mut(&mut self, key: &str) -> Result<&mut SmartRoom, RoomAccessError> {
+        self.rooms
+            .get_mut(key)
+            .ok_or_else(|| RoomAccessError::room_not_found(key, &self.name))
+    }
+}
+
+/// Creates a `SmartHome` from `{name, room}` pairs
+///
+/// Duplicate names are not reported: the last room with a given name wins.
+/// Use `try_create_home!` when duplicates should be treated as an error.
+#[macro_export]
+macro_rules! create_home {
+    ($name:expr, $({ $key:expr , $value:expr }),* $(,)? ) => {{
+        let rooms = std::collections::HashMap::from(
+          [
+            $( ($key.to_string(), $value) ),*
+          ]
+        );
+        SmartHome::new($name.to_string(), rooms)
+    }};
+}
+
+/// Creates a `SmartHome` from `{name, room}` pairs, returning a `Result`
+///
+/// Duplicate names are detected at runtime and reported as a `DuplicateKeyError`
+/// instead of silently keeping the last room as `create_home!` does.
+#[macro_export]
+macro_rules! try_create_home {
+    ($name:expr, $({ $key:expr , $value:expr }),* $(,)? ) => {{
+        $crate::smart_home::SmartHome::try_new(
+            $name.to_string(),
+            [
+              $( ($key.to_string(), $value) ),*
+            ],
+        )
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::create_room;
+    use crate::locale::Lang;
+    use crate::smart_devices::{
+        Celsius, Device, DeviceKind, DeviceTypeError, OutletDevice, OutletState, TemperatureSensor,
+        Watt,
+    };
+    use crate::smart_home::{
+        AccessRoom, AccessRoomMut, Command, DeviceAccessError, HomeEvent, HomeSnapshot,
+        HomeVisitor, RoomAccessError, SmartHome, StateHistogram,
+    };
+    use crate::smart_room::{MergeStrategy, SmartRoom};
+    use crate::traits::Information;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn three_room_home() -> SmartHome {
+        create_home!(
+            "My Smart Home",
+            {
+                "Bedroom",
+                create_room!(
+                    "Bedroom",
+                    "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
+                    "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
+                    "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius)
+                )
+            },
+            {
+                "Living Room",
+                create_room!(
+                    "Living Room",
+                    "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+                    "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+                    "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+                )
+            },
+            {
+                "Kitchen Room",
+                create_room!(
+                    "Kitchen Room",
+                    "Refrigerator Outlet" => Device::new_outlet("Refrigerator Outlet".to_string(), OutletState::On, 100 as Watt),
+                    "Teapot Outlet" => Device::new_outlet("Teapot Outlet".to_string(), OutletState::Off, 150 as Watt),
+                    "Kitchen thermometer" => Device::new_thermometer("Kitchen thermometer".to_string(), 20.0 as Celsius)
+                )
+            }
+        )
+    }
+
+    #[test]
+    fn view_home_rooms() {
+        let room1 = SmartRoom::new("Living Room".to_string(), HashMap::new());
+        let room2 = SmartRoom::new("Bedroom".to_string(), HashMap::new());
+        let home = SmartHome::new(
+            "My Home".to_string(),
+            HashMap::from([
+                ("Living Room".to_string(), room1),
+                ("Bedroom".to_string(), room2),
+            ]),
+        );
+
+        assert_eq!(home.view_room("Living Room").unwrap().name(), "Living Room");
+        assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
+    }
+
+    #[test]
+    fn create_home_test() {
+        {
+            let home = create_home!(
+                "My Smart Home",
+                {"Living Room",
+                SmartRoom::new("Living Room".to_string(), HashMap::new())},
+                {"Bedroom",
+                SmartRoom::new("Bedroom".to_string(), HashMap::new())},
+
+            );
+
+            assert_eq!(home.name(), "My Smart Home");
+            assert_eq!(home.view_room("Living Room").unwrap().name(), "Living Room");
+            assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
+        }
+        {
+            let home = create_home!(
+                "My Smart Home",
+                {
+                    "Bedroom",
+                    create_room!(
+                        "Bedroom",
+                        "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
+                        "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
+                        "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius))
+                },
+            );
+
+            assert_eq!(home.name(), "My Smart Home");
+            assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
+            assert!(home.view_room("Living Room").is_none());
+        }
+    }
+
+    #[test]
+    fn view_home_devices() {
+        let bedroom = create_room!(
+            "Bedroom",
+            "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
+            "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
+            "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius)
+        );
+
+        let home = SmartHome::new(
+            "My Smart Home".to_string(),
+            HashMap::from([("Bedroom".to_string(), bedroom)]),
+        );
+
+        assert_eq!(
+            home.view_room("Bedroom")
+                .unwrap()
+                .view_device("Attached Outlet")
+                .unwrap()
+                .name(),
+            "Attached Outlet"
+        );
+        assert_eq!(
+            home.view_room("Bedroom")
+                .unwrap()
+                .view_device("Attached Outlet")
+                .unwrap()
+                .info(),
+            "Smart Outlet: Attached Outlet - Current State: On, Power Usage: 250 Watt"
+        );
+    }
+
+    #[test]
+    fn smart_home_base_test() {
+        let home = create_home!(
+            "My Smart Home",
+            {
+                "Bedroom",
+                create_room!(
+                    "Bedroom",
+                    "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
+                    "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
+                    "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius)
+                )
+            },
+            {
+                "Living Room",
+                create_room!(
+                    "Living Room",
+                    "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+                    "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+                    "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+                )
+            },
+            {
+                "Kitchen Room",
+                create_room!(
+                    "Kitchen Room",
+                    "Refrigerator Outlet" => Device::new_outlet("Refrigerator Outlet".to_string(), OutletState::On, 100 as Watt),
+                    "Teapot Outlet" => Device::new_outlet("Teapot Outlet".to_string(), OutletState::Off, 150 as Watt),
+                    "Kitchen thermometer" => Device::new_thermometer("Kitchen thermometer".to_string(), 20.0 as Celsius)
+                )
+            }
+        );
+
+        assert_eq!(home.name(), "My Smart Home");
+        assert_eq!(home.rooms.len(), 3);
+        assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
+        assert_eq!(home.view_room("Living Room").unwrap().name(), "Living Room");
+        assert_eq!(
+            home.view_room("Kitchen Room").unwrap().name(),
+            "Kitchen Room"
+        );
+
+        assert_eq!(
+            home.view_room("Bedroom")
+                .unwrap()
+                .view_device("Attached Outlet")
+                .unwrap()
+                .name(),
+            "Attached Outlet"
+        );
+        assert_eq!(
+            home.view_room("Bedroom")
+                .unwrap()
+                .view_device("Attached Outlet")
+                .unwrap()
+                .info(),
+            "Smart Outlet: Attached Outlet - Current State: On, Power Usage: 250 Watt"
+        );
+        assert_eq!(
+            home.view_room("Bedroom")
+                .unwrap()
+                .view_device("Light Outlet")
+                .unwrap()
+                .name(),
+            "Light Outlet"
+        );
+        assert_eq!(
+            home.view_room("Bedroom")
+                .unwrap()
+                .view_device("Light Outlet")
+                .unwrap()
+                .info(),
+            "Smart Outlet: Light Outlet - Current State: Off, Power Usage: 0 Watt"
+        );
+        assert_eq!(
+            home.view_room("Bedroom")
+                .unwrap()
+                .view_device("Electron thermometer")
+                .unwrap()
+                .name(),
+            "Electron thermometer"
+        );
+        assert_eq!(
+            home.view_room("Bedroom")
+                .unwrap()
+                .view_device("Electron thermometer")
+                .unwrap()
+                .info(),
+            "Thermometer: Electron thermometer - Current Temperature: 22.50°C"
+        );
+
+        assert_eq!(
+            home.view_room("Living Room")
+                .unwrap()
+                .view_device("Lighter")
+                .unwrap()
+                .name(),
+            "Lighter"
+        );
+        assert_eq!(
+            home.view_room("Living Room")
+                .unwrap()
+                .view_device("Lighter")
+                .unwrap()
+                .info(),
+            "Smart Outlet: Lighter - Current State: On, Power Usage: 100 Watt"
+        );
+        assert_eq!(
+            home.view_room("Living Room")
+                .unwrap()
+                .view_device("PC")
+                .unwrap()
+                .name(),
+            "PC"
+        );
+        assert_eq!(
+            home.view_room("Living Room")
+                .unwrap()
+                .view_device("PC")
+                .unwrap()
+                .info(),
+            "Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"
+        );
+        assert_eq!(
+            home.view_room("Living Room")
+                .unwrap()
+                .view_device("Electronic thermometer")
+                .unwrap()
+                .name(),
+            "Electronic thermometer"
+        );
+        assert_eq!(
+            home.view_room("Living Room")
+                .unwrap()
+                .view_device("Electronic thermometer")
+                .unwrap()
+                .info(),
+            "Thermometer: Electronic thermometer - Current Temperature: 22.50°C"
+        );
+
+        assert_eq!(
+            home.view_room("Kitchen Room")
+                .unwrap()
+                .view_device("Refrigerator Outlet")
+                .unwrap()
+                .name(),
+            "Refrigerator Outlet"
+        );
+        assert_eq!(
+            home.view_room("Kitchen Room")
+                .unwrap()
+                .view_device("Refrigerator Outlet")
+                .unwrap()
+                .info(),
+            "Smart Outlet: Refrigerator Outlet - Current State: On, Power Usage: 100 Watt"
+        );
+        assert_eq!(
+            home.view_room("Kitchen Room")
+                .unwrap()
+                .view_device("Teapot Outlet")
+                .unwrap()
+                .name(),
+            "Teapot Outlet"
+        );
+        assert_eq!(
+            home.view_room("Kitchen Room")
+                .unwrap()
+                .view_device("Teapot Outlet")
+                .unwrap()
+                .info(),
+            "Smart Outlet: Teapot Outlet - Current State: Off, Power Usage: 0 Watt"
+        );
+        assert_eq!(
+            home.view_room("Kitchen Room")
+                .unwrap()
+                .view_device("Kitchen thermometer")
+                .unwrap()
+                .name(),
+            "Kitchen thermometer"
+        );
+        assert_eq!(
+            home.view_room("Kitchen Room")
+                .unwrap()
+                .view_device("Kitchen thermometer")
+                .unwrap()
+                .info(),
+            "Thermometer: Kitchen thermometer - Current Temperature: 20.00°C"
+        );
+
+        let expected = r#"Smart Home: My Smart Home:
+ Total Rooms: 3
+
+Room[0]:
+Smart Room: Bedroom:
+ Total devices: 3
+  [0]: Smart Outlet: Attached Outlet - Current State: On, Power Usage: 250 Watt
+  --------------------------------------
+  [1]: Thermometer: Electron thermometer - Current Temperature: 22.50°C
+  --------------------------------------
+  [2]: Smart Outlet: Light Outlet - Current State: Off, Power Usage: 0 Watt
+=====================================
+Room[1]:
+Smart Room: Kitchen Room:
+ Total devices: 3
+  [0]: Thermometer: Kitchen thermometer - Current Temperature: 20.00°C
+  --------------------------------------
+  [1]: Smart Outlet: Refrigerator Outlet - Current State: On, Power Usage: 100 Watt
+  --------------------------------------
+  [2]: Smart Outlet: Teapot Outlet - Current State: Off, Power Usage: 0 Watt
+=====================================
+Room[2]:
+Smart Room: Living Room:
+ Total devices: 3
+  [0]: Thermometer: Electronic thermometer - Current Temperature: 22.50°C
+  --------------------------------------
+  [1]: Smart Outlet: Lighter - Current State: On, Power Usage: 100 Watt
+  --------------------------------------
+  [2]: Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"#;
+
+        assert_eq!(home.info(), expected);
+    }
+
     #[test]
-    fn view_home_devices() {
+    fn smart_home_add_rooms_test() {
+        let mut home = SmartHome::new("My Home".to_string(), HashMap::new());
+        let bedroom = SmartRoom::new("Bedroom".to_string(), HashMap::new());
+        home.add_room(bedroom);
+        assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
+
+        let living_room = SmartRoom::new("Living Room".to_string(), HashMap::new());
+        home.add_room(living_room);
+        assert_eq!(home.view_room("Living Room").unwrap().name(), "Living Room");
+
+        assert_eq!(home.rooms.len(), 2);
+    }
+
+    #[test]
+    fn smart_home_remove_rooms_test() {
+        let mut home = SmartHome::new("My Home".to_string(), HashMap::new());
+        let bedroom = SmartRoom::new("Bedroom".to_string(), HashMap::new());
+        home.add_room(bedroom);
+        assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
+
+        home.remove_room("Bedroom");
+        assert!(home.view_room("Bedroom").is_none());
+
+        assert_eq!(home.rooms.len(), 0);
+    }
+
+    #[test]
+    fn smart_home_access_room_test() {
+        let bedroom = SmartRoom::new("Bedroom".to_string(), HashMap::new());
+        let living_room = SmartRoom::new("Living Room".to_string(), HashMap::new());
+        let home = SmartHome::new(
+            "My Home".to_string(),
+            HashMap::from([
+                ("Bedroom".to_string(), bedroom),
+                ("Living Room".to_string(), living_room),
+            ]),
+        );
+
+        assert!(home.access_room("Bedroom").is_ok());
+        assert!(home.access_room("Living Room").is_ok());
+        assert!(home.access_room("Kitchen").is_err());
+
+        assert_eq!(
+            home.access_room("Kitchen").unwrap_err(),
+            RoomAccessError {
+                message: "Room with the name 'Kitchen' not found in the house 'My Home'"
+                    .to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn smart_home_access_device_test() {
+        let bedroom = SmartRoom::new(
+            "Bedroom".to_string(),
+            HashMap::from([(
+                "Attached Outlet".to_string(),
+                Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
+            )]),
+        );
+        let home = SmartHome::new(
+            "My Home".to_string(),
+            HashMap::from([("Bedroom".to_string(), bedroom)]),
+        );
+
+        assert!(home.device("Bedroom", "Attached Outlet").is_ok());
+        assert!(home.device("Bedroom", "Nonexistent Device").is_err());
+        assert_eq!(
+            home.device("Bedroom", "Nonexistent Device").unwrap_err(),
+            DeviceAccessError::DeviceAccess(crate::smart_room::AccessError::device_not_found(
+                "Nonexistent Device",
+                "Bedroom"
+            ))
+        );
+    }
+
+    #[test]
+    fn smart_room_get_ne_exist_room_test() {
+        let home = SmartHome::new("My Home".to_string(), HashMap::new());
+        let none = home.view_room("Some room");
+        assert!(none.is_none());
+    }
+
+    #[test]
+    fn smart_home_switch_outlet_test() {
         let bedroom = create_room!(
             "Bedroom",
             "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
             "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
             "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius)
         );
+        let living_room = create_room!(
+            "Living Room",
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+        );
+        let mut home = create_home!(
+            "My Home",
+            {"Bedroom", bedroom},
+            {"Living Room", living_room}
+        );
 
-        let home = SmartHome::new(
-            "My Smart Home".to_string(),
-            HashMap::from([("Bedroom".to_string(), bedroom)]),
+        assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
+        assert_eq!(home.view_room("Living Room").unwrap().name(), "Living Room");
+
+        assert_eq!(
+            home.view_room("Bedroom")
+                .unwrap()
+                .view_device("Attached Outlet")
+                .unwrap()
+                .info(),
+            "Smart Outlet: Attached Outlet - Current State: On, Power Usage: 250 Watt"
+        );
+
+        {
+            let device = home
+                .get_room("Bedroom")
+                .unwrap()
+                .get_device("Attached Outlet")
+                .unwrap();
+            let outlet = match device {
+                Device::OutletType(o) => o,
+                _ => panic!("Expected OutletType"),
+            };
+            assert_eq!(outlet.state(), OutletState::On);
+            outlet.switch();
+            assert_eq!(outlet.state(), OutletState::Off);
+        }
+        assert_eq!(
+            home.view_room("Bedroom")
+                .unwrap()
+                .view_device("Attached Outlet")
+                .unwrap()
+                .info(),
+            "Smart Outlet: Attached Outlet - Current State: Off, Power Usage: 0 Watt"
+        );
+    }
+    #[test]
+    fn smart_home_changed_info_test() {
+        let mut home = create_home!(
+            "My Smart Home",
+            {
+                "Bedroom",
+                create_room!(
+                    "Bedroom",
+                    "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
+                    "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
+                    "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius)
+                )
+            },
+            {
+                "Living Room",
+                create_room!(
+                    "Living Room",
+                    "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+                    "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+                    "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+                )
+            },
+            {
+                "Kitchen Room",
+                create_room!(
+                    "Kitchen Room",
+                    "Refrigerator Outlet" => Device::new_outlet("Refrigerator Outlet".to_string(), OutletState::On, 100 as Watt),
+                    "Teapot Outlet" => Device::new_outlet("Teapot Outlet".to_string(), OutletState::Off, 150 as Watt),
+                    "Kitchen thermometer" => Device::new_thermometer("Kitchen thermometer".to_string(), 20.0 as Celsius)
+                )
+            }
+        );
+
+        let expected = r#"Smart Home: My Smart Home:
+ Total Rooms: 3
+
+Room[0]:
+Smart Room: Bedroom:
+ Total devices: 3
+  [0]: Smart Outlet: Attached Outlet - Current State: On, Power Usage: 250 Watt
+  --------------------------------------
+  [1]: Thermometer: Electron thermometer - Current Temperature: 22.50°C
+  --------------------------------------
+  [2]: Smart Outlet: Light Outlet - Current State: Off, Power Usage: 0 Watt
+=====================================
+Room[1]:
+Smart Room: Kitchen Room:
+ Total devices: 3
+  [0]: Thermometer: Kitchen thermometer - Current Temperature: 20.00°C
+  --------------------------------------
+  [1]: Smart Outlet: Refrigerator Outlet - Current State: On, Power Usage: 100 Watt
+  --------------------------------------
+  [2]: Smart Outlet: Teapot Outlet - Current State: Off, Power Usage: 0 Watt
+=====================================
+Room[2]:
+Smart Room: Living Room:
+ Total devices: 3
+  [0]: Thermometer: Electronic thermometer - Current Temperature: 22.50°C
+  --------------------------------------
+  [1]: Smart Outlet: Lighter - Current State: On, Power Usage: 100 Watt
+  --------------------------------------
+  [2]: Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"#;
+
+        assert_eq!(home.info(), expected);
+
+        {
+            let kitchen_room = home.get_room("Kitchen Room").unwrap();
+            let device = kitchen_room.get_device("Teapot Outlet").unwrap();
+            let outlet = match device {
+                Device::OutletType(outlet) => outlet,
+                _ => panic!("Expected OutletType"),
+            };
+            assert_eq!(outlet.state(), OutletState::Off);
+            outlet.switch();
+            assert_eq!(outlet.state(), OutletState::On);
+        }
+
+        let expected = r#"Smart Home: My Smart Home:
+ Total Rooms: 3
+
+Room[0]:
+Smart Room: Bedroom:
+ Total devices: 3
+  [0]: Smart Outlet: Attached Outlet - Current State: On, Power Usage: 250 Watt
+  --------------------------------------
+  [1]: Thermometer: Electron thermometer - Current Temperature: 22.50°C
+  --------------------------------------
+  [2]: Smart Outlet: Light Outlet - Current State: Off, Power Usage: 0 Watt
+=====================================
+Room[1]:
+Smart Room: Kitchen Room:
+ Total devices: 3
+  [0]: Thermometer: Kitchen thermometer - Current Temperature: 20.00°C
+  --------------------------------------
+  [1]: Smart Outlet: Refrigerator Outlet - Current State: On, Power Usage: 100 Watt
+  --------------------------------------
+  [2]: Smart Outlet: Teapot Outlet - Current State: On, Power Usage: 150 Watt
+=====================================
+Room[2]:
+Smart Room: Living Room:
+ Total devices: 3
+  [0]: Thermometer: Electronic thermometer - Current Temperature: 22.50°C
+  --------------------------------------
+  [1]: Smart Outlet: Lighter - Current State: On, Power Usage: 100 Watt
+  --------------------------------------
+  [2]: Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"#;
+
+        assert_eq!(home.info(), expected);
+    }
+
+    #[test]
+    fn smart_home_iter_devices_test() {
+        let home = three_room_home();
+
+        let devices: Vec<(&str, &str)> = home
+            .iter_devices()
+            .map(|(room, device, _)| (room, device))
+            .collect();
+        assert_eq!(devices.len(), 9);
+        assert_eq!(
+            devices,
+            vec![
+                ("Bedroom", "Attached Outlet"),
+                ("Bedroom", "Electron thermometer"),
+                ("Bedroom", "Light Outlet"),
+                ("Kitchen Room", "Kitchen thermometer"),
+                ("Kitchen Room", "Refrigerator Outlet"),
+                ("Kitchen Room", "Teapot Outlet"),
+                ("Living Room", "Electronic thermometer"),
+                ("Living Room", "Lighter"),
+                ("Living Room", "PC"),
+            ]
         );
+        assert!(
+            home.iter_devices()
+                .all(|(_, name, device)| device.name() == name)
+        );
+    }
+
+    #[test]
+    fn smart_home_total_power_usage_test() {
+        let mut home = three_room_home();
+        assert_eq!(home.total_power_usage(), 700 as Watt);
 
-        assert_eq!(
-            home.view_room("Bedroom")
-                .unwrap()
-                .view_device("Attached Outlet")
-                .unwrap()
-                .name(),
-            "Attached Outlet"
+        home.remove_room("Living Room");
+        assert_eq!(home.total_power_usage(), 350 as Watt);
+    }
+
+    #[test]
+    fn try_create_home_duplicate_keys_test() {
+        let home = try_create_home!(
+            "My Home",
+            {"Bedroom", SmartRoom::new("Bedroom".to_string(), HashMap::new())},
+            {"Kitchen", SmartRoom::new("Kitchen".to_string(), HashMap::new())},
+        )
+        .unwrap();
+        assert_eq!(home.rooms.len(), 2);
+
+        let result = try_create_home!(
+            "My Home",
+            {"Bedroom", SmartRoom::new("Bedroom".to_string(), HashMap::new())},
+            {"Bedroom", SmartRoom::new("Bedroom".to_string(), HashMap::new())},
         );
         assert_eq!(
-            home.view_room("Bedroom")
-                .unwrap()
-                .view_device("Attached Outlet")
-                .unwrap()
-                .info(),
-            "Smart Outlet: Attached Outlet - Current State: On, Power Usage: 250 Watt"
+            result.unwrap_err().to_string(),
+            "smart-home error: Room name 'Bedroom' is used more than once in the house 'My Home'"
         );
     }
 
     #[test]
-    fn smart_home_base_test() {
+    fn smart_home_to_dot_test() {
         let home = create_home!(
-            "My Smart Home",
+            "My \"Smart\" Home",
             {
                 "Bedroom",
                 create_room!(
                     "Bedroom",
-                    "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
                     "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
-                    "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius)
+                    "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt)
                 )
             },
+            {"Attic", create_room!("Attic",)}
+        );
+
+        let expected = r#"digraph "My \"Smart\" Home" {
+    "home" [label="My \"Smart\" Home"];
+    "room:Attic" [label="Attic"];
+    "home" -> "room:Attic";
+    "room:Bedroom" [label="Bedroom"];
+    "home" -> "room:Bedroom";
+    "device:Bedroom/Attached Outlet" [label="Attached Outlet"];
+    "room:Bedroom" -> "device:Bedroom/Attached Outlet";
+    "device:Bedroom/Light Outlet" [label="Light Outlet"];
+    "room:Bedroom" -> "device:Bedroom/Light Outlet";
+}"#;
+        assert_eq!(home.to_dot(), expected);
+    }
+
+    #[test]
+    fn smart_home_info_localized_test() {
+        let home = create_home!(
+            "My Home",
             {
-                "Living Room",
+                "Bedroom",
                 create_room!(
-                    "Living Room",
-                    "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
-                    "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
-                    "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+                    "Bedroom",
+                    "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
+                    "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius)
                 )
-            },
+            }
+        );
+
+        let expected_en = r#"Smart Home: My Home:
+ Total Rooms: 1
+
+Room[0]:
+Smart Room: Bedroom:
+ Total devices: 2
+  [0]: Thermometer: Electron thermometer - Current Temperature: 22.50°C
+  --------------------------------------
+  [1]: Smart Outlet: Light Outlet - Current State: Off, Power Usage: 0 Watt"#;
+        assert_eq!(home.info_localized(Lang::En), expected_en);
+        assert_eq!(home.info(), expected_en);
+
+        let expected_ru = r#"Умный дом: My Home:
+ Всего комнат: 1
+
+Комната[0]:
+Умная комната: Bedroom:
+ Всего устройств: 2
+  [0]: Термометр: Electron thermometer - Текущая температура: 22.50°C
+  --------------------------------------
+  [1]: Умная розетка: Light Outlet - Текущее состояние: Выкл, Потребляемая мощность: 0 Вт"#;
+        assert_eq!(home.info_localized(Lang::Ru), expected_ru);
+    }
+
+    #[test]
+    fn smart_home_largest_consumer_test() {
+        let mut home = three_room_home();
+        assert_eq!(
+            home.largest_consumer(),
+            Some(("Bedroom".to_string(), "Attached Outlet".to_string(), 250))
+        );
+
+        home.remove_room("Bedroom");
+        assert_eq!(
+            home.largest_consumer(),
+            Some(("Living Room".to_string(), "PC".to_string(), 250))
+        );
+
+        home.remove_room("Living Room");
+        assert_eq!(
+            home.largest_consumer(),
+            Some((
+                "Kitchen Room".to_string(),
+                "Refrigerator Outlet".to_string(),
+                100
+            ))
+        );
+
+        home.remove_room("Kitchen Room");
+        assert_eq!(home.largest_consumer(), None);
+    }
+
+    #[test]
+    fn smart_home_set_name_test() {
+        let mut home = three_room_home();
+        home.set_name("Country House".to_string());
+        assert_eq!(home.name(), "Country House");
+        assert!(
+            home.info()
+                .starts_with("Smart Home: Country House:\n Total Rooms: 3")
+        );
+    }
+
+    #[test]
+    fn smart_home_rename_room_test() {
+        let mut home = three_room_home();
+        let receiver = home.subscribe();
+        assert!(
+            home.rename_room("Kitchen Room", "Kitchen".to_string())
+                .is_ok()
+        );
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<HomeEvent>>(),
+            vec![
+                HomeEvent::RoomRemoved("Kitchen Room".to_string()),
+                HomeEvent::RoomAdded("Kitchen".to_string()),
+            ]
+        );
+        assert!(home.view_room("Kitchen Room").is_none());
+        assert_eq!(home.view_room("Kitchen").unwrap().name(), "Kitchen");
+        assert!(home.info().contains("Smart Room: Kitchen:"));
+
+        assert_eq!(
+            home.rename_room("Attic", "Loft".to_string()).unwrap_err(),
+            RoomAccessError {
+                message: "Room with the name 'Attic' not found in the house 'My Smart Home'"
+                    .to_string()
+            }
+        );
+        assert_eq!(
+            home.rename_room("Kitchen", "Bedroom".to_string())
+                .unwrap_err(),
+            RoomAccessError {
+                message: "Room with the name 'Bedroom' already exists in the house 'My Smart Home'"
+                    .to_string()
+            }
+        );
+        assert_eq!(home.rooms.len(), 3);
+        assert!(home.rename_room("Kitchen", "Kitchen".to_string()).is_ok());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn smart_home_event_log_test() {
+        let mut home = three_room_home();
+        home.add_room(SmartRoom::new("Attic".to_string(), HashMap::new()));
+        assert!(home.events().is_empty());
+
+        home.enable_event_log();
+        home.add_room(SmartRoom::new("Garage".to_string(), HashMap::new()));
+        home.update_device("Living Room", "PC", |device| {
+            if let Device::OutletType(outlet) = device {
+                outlet.turn_off();
+            }
+        })
+        .unwrap();
+        assert!(home.update_device("Living Room", "TV", |_| {}).is_err());
+        assert!(home.remove_room("Cellar").is_none());
+        home.remove_room("Attic");
+
+        assert_eq!(
+            home.events(),
+            &[
+                HomeEvent::RoomAdded("Garage".to_string()),
+                HomeEvent::DeviceChanged {
+                    room: "Living Room".to_string(),
+                    device: "PC".to_string()
+                },
+                HomeEvent::RoomRemoved("Attic".to_string()),
+            ]
+        );
+        assert_eq!(
+            home.device("Living Room", "PC").unwrap().info(),
+            "Smart Outlet: PC - Current State: Off, Power Usage: 0 Watt"
+        );
+    }
+
+    #[test]
+    fn smart_home_update_device_errors_test() {
+        let mut home = three_room_home();
+        assert_eq!(
+            home.update_device("Attic", "PC", |_| {}).unwrap_err(),
+            DeviceAccessError::RoomAccess(RoomAccessError {
+                message: "Room with the name 'Attic' not found in the house 'My Smart Home'"
+                    .to_string()
+            })
+        );
+        assert_eq!(
+            home.update_device("Bedroom", "PC", |_| {}).unwrap_err(),
+            DeviceAccessError::DeviceAccess(crate::smart_room::AccessError::device_not_found(
+                "PC", "Bedroom"
+            ))
+        );
+    }
+
+    #[test]
+    fn smart_home_flatten_test() {
+        let home = three_room_home();
+        let flat = home.flatten();
+
+        assert_eq!(flat.len(), 9);
+        assert_eq!(
+            flat["Living Room/PC"].info(),
+            "Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"
+        );
+        assert_eq!(
+            flat.get("Kitchen Room/Kitchen thermometer")
+                .map(|d| d.name()),
+            Some("Kitchen thermometer".to_string())
+        );
+        assert!(!flat.contains_key("PC"));
+        assert_eq!(flat.keys().next().unwrap(), "Bedroom/Attached Outlet");
+    }
+
+    #[test]
+    fn smart_home_group_by_kind_test() {
+        let mut home = three_room_home();
+        assert_eq!(
+            home.group_by_kind(),
+            BTreeMap::from([(DeviceKind::Outlet, 6), (DeviceKind::Thermometer, 3)])
+        );
+
+        home.get_room("Bedroom")
+            .unwrap()
+            .add_device("Placeholder".to_string(), Device::Empty);
+        let groups = home.group_by_kind();
+        assert_eq!(groups[&DeviceKind::Empty], 1);
+        assert_eq!(
+            groups
+                .iter()
+                .map(|(kind, count)| format!("{}: {}", kind, count))
+                .collect::<Vec<_>>(),
+            vec!["Outlet: 6", "Thermometer: 3", "Empty: 1"]
+        );
+    }
+
+    #[test]
+    fn smart_home_validate_test() {
+        let mut home = three_room_home();
+        assert_eq!(home.validate(), Ok(()));
+
+        home.get_room("Kitchen Room")
+            .unwrap()
+            .set_name("Kitchen".to_string());
+        home.add_room(SmartRoom::new(String::new(), HashMap::new()));
+        assert_eq!(
+            home.validate(),
+            Err(vec![
+                "Room has an empty name".to_string(),
+                "Room 'Kitchen' is stored under the key 'Kitchen Room' in the house 'My Smart Home'"
+                    .to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn smart_home_to_json_manual_test() {
+        let mut home = three_room_home();
+        home.get_room("Bedroom")
+            .unwrap()
+            .add_device("Placeholder".to_string(), Device::Empty);
+        home.set_name("My \"Smart\" Home".to_string());
+
+        let json = home.to_json_manual();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["name"], "My \"Smart\" Home");
+        assert_eq!(value["rooms"].as_object().unwrap().len(), 3);
+        let bedroom = &value["rooms"]["Bedroom"];
+        assert_eq!(bedroom["name"], "Bedroom");
+        assert_eq!(bedroom["devices"].as_object().unwrap().len(), 4);
+        assert_eq!(
+            bedroom["devices"]["Attached Outlet"],
+            serde_json::json!({
+                "kind": "outlet",
+                "name": "Attached Outlet",
+                "state": "On",
+                "power_usage": 250,
+                "standby_power": 0,
+                "on_seconds": 0.0,
+                "expected_power": null
+            })
+        );
+        assert_eq!(
+            bedroom["devices"]["Electron thermometer"],
+            serde_json::json!({
+                "kind": "thermometer",
+                "name": "Electron thermometer",
+                "temperature": 22.5
+            })
+        );
+        assert_eq!(
+            bedroom["devices"]["Placeholder"],
+            serde_json::json!({ "kind": "empty" })
+        );
+        assert!(json.starts_with(r#"{"name":"My \"Smart\" Home","rooms":{"Bedroom":"#));
+    }
+
+    #[test]
+    fn smart_home_subscribe_test() {
+        let mut home = three_room_home();
+        let receiver = home.subscribe();
+        let dropped = home.subscribe();
+        drop(dropped);
+
+        home.update_device("Living Room", "PC", |device| {
+            if let Device::OutletType(outlet) = device {
+                outlet.switch();
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(HomeEvent::DeviceChanged {
+                room: "Living Room".to_string(),
+                device: "PC".to_string()
+            })
+        );
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(home.subscribers.0.len(), 1);
+        assert!(home.events().is_empty());
+
+        let copy = home.clone();
+        assert!(copy.subscribers.0.is_empty());
+
+        home.remove_room("Bedroom");
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(HomeEvent::RoomRemoved("Bedroom".to_string()))
+        );
+    }
+
+    #[test]
+    fn smart_home_access_room_mut_test() {
+        let mut home = three_room_home();
+
+        let room = home.access_room_mut("Bedroom").unwrap();
+        room.remove_device("Light Outlet");
+        room.add_device(
+            "Lamp".to_string(),
+            Device::new_outlet("Lamp".to_string(), OutletState::On, 60 as Watt),
+        );
+        assert!(home.device("Bedroom", "Light Outlet").is_err());
+        assert!(home.device("Bedroom", "Lamp").is_ok());
+
+        assert_eq!(
+            home.access_room_mut("Garage").unwrap_err(),
+            RoomAccessError {
+                message: "Room with the name 'Garage' not found in the house 'My Smart Home'"
+                    .to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn smart_home_power_per_room_test() {
+        let mut home = three_room_home();
+        home.add_room(SmartRoom::new("Attic".to_string(), HashMap::new()));
+        home.add_room(create_room!(
+            "Garage",
+            "Charger" => Device::new_outlet("Charger".to_string(), OutletState::On, 100 as Watt)
+        ));
+
+        assert_eq!(
+            home.power_per_room(),
+            vec![
+                ("Living Room".to_string(), 350),
+                ("Bedroom".to_string(), 250),
+                ("Garage".to_string(), 100),
+                ("Kitchen Room".to_string(), 100),
+                ("Attic".to_string(), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn smart_home_checked_total_power_test() {
+        let home = three_room_home();
+        assert_eq!(home.checked_total_power(), Some(home.total_power_usage()));
+
+        let home = create_home!(
+            "Power Plant",
             {
-                "Kitchen Room",
+                "Hall",
                 create_room!(
-                    "Kitchen Room",
-                    "Refrigerator Outlet" => Device::new_outlet("Refrigerator Outlet".to_string(), OutletState::On, 100 as Watt),
-                    "Teapot Outlet" => Device::new_outlet("Teapot Outlet".to_string(), OutletState::Off, 150 as Watt),
-                    "Kitchen thermometer" => Device::new_thermometer("Kitchen thermometer".to_string(), 20.0 as Celsius)
+                    "Hall",
+                    "Turbine A" => Device::new_outlet("Turbine A".to_string(), OutletState::On, Watt::MAX - 10),
+                    "Turbine B" => Device::new_outlet("Turbine B".to_string(), OutletState::On, 20 as Watt)
+                )
+            }
+        );
+        assert_eq!(home.checked_total_power(), None);
+        assert_eq!(home.total_power_usage(), Watt::MAX);
+    }
+
+    #[test]
+    fn device_access_error_into_io_error_test() {
+        let home = three_room_home();
+
+        let error: std::io::Error = home.device("Bedroom", "PC").unwrap_err().into();
+        assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+        assert_eq!(
+            error.to_string(),
+            "smart-home error: Device with the name 'PC' not found in the room 'Bedroom'"
+        );
+
+        let error: std::io::Error = home.device("Garage", "PC").unwrap_err().into();
+        assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+
+        fn lookup(home: &SmartHome) -> std::io::Result<String> {
+            Ok(home.device("Attic", "Lamp")?.name())
+        }
+        assert_eq!(
+            lookup(&home).unwrap_err().to_string(),
+            "smart-home error: Room with the name 'Attic' not found in the house 'My Smart Home'"
+        );
+    }
+
+    #[test]
+    fn smart_home_offset_all_thermometers_test() {
+        let original = three_room_home();
+        let mut home = original.clone();
+        let receiver = home.subscribe();
+        home.offset_all_thermometers(1.5 as Celsius);
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<HomeEvent>>(),
+            vec![
+                HomeEvent::DeviceChanged {
+                    room: "Bedroom".to_string(),
+                    device: "Electron thermometer".to_string()
+                },
+                HomeEvent::DeviceChanged {
+                    room: "Kitchen Room".to_string(),
+                    device: "Kitchen thermometer".to_string()
+                },
+                HomeEvent::DeviceChanged {
+                    room: "Living Room".to_string(),
+                    device: "Electronic thermometer".to_string()
+                },
+            ]
+        );
+        let Device::ThermometerType(bedroom) =
+            home.device("Bedroom", "Electron thermometer").unwrap()
+        else {
+            panic!("expected a thermometer");
+        };
+        assert_eq!(bedroom.last_updated(), None);
+
+        for ((_, _, before), (room, name, after)) in
+            original.iter_devices().zip(home.iter_devices())
+        {
+            match (before, after) {
+                (Device::ThermometerType(before), Device::ThermometerType(after)) => {
+                    assert_eq!(
+                        after.current_temperature(),
+                        before.current_temperature() + 1.5,
+                        "{room}/{name}"
+                    );
+                }
+                _ => assert_eq!(before, after, "{room}/{name}"),
+            }
+        }
+        assert_eq!(
+            home.device("Kitchen Room", "Kitchen thermometer")
+                .unwrap()
+                .info(),
+            "Thermometer: Kitchen thermometer - Current Temperature: 21.50°C"
+        );
+        assert_eq!(home.total_power_usage(), original.total_power_usage());
+    }
+
+    #[test]
+    fn smart_home_rooms_sorted_test() {
+        let home = three_room_home();
+        let names: Vec<String> = home.rooms_sorted().iter().map(|room| room.name()).collect();
+        assert_eq!(names, vec!["Bedroom", "Kitchen Room", "Living Room"]);
+        assert!(
+            SmartHome::new("Empty".to_string(), HashMap::new())
+                .rooms_sorted()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn smart_home_remove_device_test() {
+        let mut home = three_room_home();
+        let receiver = home.subscribe();
+
+        let removed = home.remove_device("Living Room", "PC").unwrap();
+        assert_eq!(removed.name(), "PC");
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(HomeEvent::DeviceChanged {
+                room: "Living Room".to_string(),
+                device: "PC".to_string()
+            })
+        );
+        assert!(home.device("Living Room", "PC").is_err());
+        assert_eq!(home.view_room("Living Room").unwrap().devices().count(), 2);
+
+        assert_eq!(
+            home.remove_device("Living Room", "PC").unwrap_err(),
+            DeviceAccessError::DeviceAccess(crate::smart_room::AccessError::device_not_found(
+                "PC",
+                "Living Room"
+            ))
+        );
+        assert_eq!(
+            home.remove_device("Garage", "PC").unwrap_err(),
+            DeviceAccessError::RoomAccess(RoomAccessError {
+                message: "Room with the name 'Garage' not found in the house 'My Smart Home'"
+                    .to_string()
+            })
+        );
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn smart_home_add_device_test() {
+        let mut home = three_room_home();
+        let receiver = home.subscribe();
+
+        let lamp = Device::new_outlet("Lamp".to_string(), OutletState::On, 60 as Watt);
+        assert!(
+            home.add_device("Bedroom", "Lamp".to_string(), lamp.clone())
+                .is_ok()
+        );
+        assert_eq!(home.device("Bedroom", "Lamp").unwrap(), &lamp);
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(HomeEvent::DeviceChanged {
+                room: "Bedroom".to_string(),
+                device: "Lamp".to_string()
+            })
+        );
+
+        assert_eq!(
+            home.add_device("Garage", "Lamp".to_string(), lamp)
+                .unwrap_err(),
+            RoomAccessError {
+                message: "Room with the name 'Garage' not found in the house 'My Smart Home'"
+                    .to_string()
+            }
+        );
+        assert!(home.view_room("Garage").is_none());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn smart_home_into_iterator_test() {
+        let home = three_room_home();
+        let mut names = Vec::new();
+        for room in &home {
+            names.push(room.name());
+        }
+        assert_eq!(names, vec!["Bedroom", "Kitchen Room", "Living Room"]);
+        assert_eq!((&home).into_iter().count(), 3);
+    }
+
+    #[test]
+    fn smart_home_merge_from_test() {
+        let mut home = three_room_home();
+        let other = create_home!(
+            "Other Home",
+            {
+                "Living Room",
+                create_room!(
+                    "Living Room",
+                    "PC" => Device::new_outlet("PC".to_string(), OutletState::Off, 300 as Watt),
+                    "TV" => Device::new_outlet("TV".to_string(), OutletState::On, 120 as Watt)
                 )
-            }
+            },
+            {"Garage", create_room!("Garage",)}
         );
 
-        assert_eq!(home.name(), "My Smart Home");
-        assert_eq!(home.rooms.len(), 3);
-        assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
-        assert_eq!(home.view_room("Living Room").unwrap().name(), "Living Room");
+        let mut kept = home.clone();
+        let kept_receiver = kept.subscribe();
+        kept.merge_from(other.clone(), MergeStrategy::KeepExisting);
+        assert_eq!(kept.name(), "My Smart Home");
+        assert_eq!(kept.rooms.len(), 4);
+        assert_eq!(kept.device("Living Room", "PC").unwrap().power_usage(), 250);
+        assert!(kept.device("Living Room", "TV").is_ok());
         assert_eq!(
-            home.view_room("Kitchen Room").unwrap().name(),
-            "Kitchen Room"
+            kept_receiver.try_iter().collect::<Vec<HomeEvent>>(),
+            vec![
+                HomeEvent::RoomAdded("Garage".to_string()),
+                HomeEvent::DeviceChanged {
+                    room: "Living Room".to_string(),
+                    device: "TV".to_string()
+                },
+            ]
         );
 
+        let receiver = home.subscribe();
+        home.merge_from(other, MergeStrategy::Overwrite);
         assert_eq!(
-            home.view_room("Bedroom")
-                .unwrap()
-                .view_device("Attached Outlet")
-                .unwrap()
-                .name(),
-            "Attached Outlet"
-        );
-        assert_eq!(
-            home.view_room("Bedroom")
-                .unwrap()
-                .view_device("Attached Outlet")
-                .unwrap()
-                .info(),
-            "Smart Outlet: Attached Outlet - Current State: On, Power Usage: 250 Watt"
+            receiver.try_iter().collect::<Vec<HomeEvent>>(),
+            vec![
+                HomeEvent::RoomAdded("Garage".to_string()),
+                HomeEvent::DeviceChanged {
+                    room: "Living Room".to_string(),
+                    device: "PC".to_string()
+                },
+                HomeEvent::DeviceChanged {
+                    room: "Living Room".to_string(),
+                    device: "TV".to_string()
+                },
+            ]
         );
+        assert_eq!(home.rooms.len(), 4);
+        assert_eq!(home.device("Living Room", "PC").unwrap().power_usage(), 0);
+        assert!(home.device("Living Room", "Lighter").is_ok());
+        assert!(home.view_room("Garage").is_some());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn smart_home_serde_matches_manual_json_test() {
+        let home = three_room_home();
+        let serde_value = serde_json::to_value(&home).unwrap();
+        let manual_value: serde_json::Value = serde_json::from_str(&home.to_json_manual()).unwrap();
+        assert_eq!(serde_value, manual_value);
+    }
+
+    #[test]
+    fn smart_home_prune_empty_rooms_test() {
+        let mut home = three_room_home();
+        home.add_room(create_room!("Garage",));
+        home.add_room(create_room!("Attic",));
+        home.enable_event_log();
+
         assert_eq!(
-            home.view_room("Bedroom")
-                .unwrap()
-                .view_device("Light Outlet")
-                .unwrap()
-                .name(),
-            "Light Outlet"
+            home.prune_empty_rooms(),
+            vec!["Attic".to_string(), "Garage".to_string()]
         );
+        assert_eq!(home.rooms.len(), 3);
+        assert!(home.view_room("Bedroom").is_some());
         assert_eq!(
-            home.view_room("Bedroom")
-                .unwrap()
-                .view_device("Light Outlet")
-                .unwrap()
-                .info(),
-            "Smart Outlet: Light Outlet - Current State: Off, Power Usage: 0 Watt"
+            home.events(),
+            &[
+                HomeEvent::RoomRemoved("Attic".to_string()),
+                HomeEvent::RoomRemoved("Garage".to_string()),
+            ]
         );
-        assert_eq!(
-            home.view_room("Bedroom")
-                .unwrap()
-                .view_device("Electron thermometer")
-                .unwrap()
-                .name(),
-            "Electron thermometer"
+        assert!(home.prune_empty_rooms().is_empty());
+    }
+
+    #[test]
+    fn smart_home_write_info_test() {
+        let home = three_room_home();
+        let mut buffer = String::new();
+        home.write_info(&mut buffer).unwrap();
+        assert_eq!(buffer, home.info());
+
+        let mut buffer = String::new();
+        home.write_info_localized(&mut buffer, Lang::Ru).unwrap();
+        assert_eq!(buffer, home.info_localized(Lang::Ru));
+
+        let empty = SmartHome::new("Empty".to_string(), HashMap::new());
+        assert_eq!(empty.info(), "Smart Home: Empty:\n Total Rooms: 0\n\n");
+    }
+
+    #[test]
+    fn smart_home_clone_room_test() {
+        let mut home = three_room_home();
+        home.clone_room("Bedroom", "Guest Bedroom").unwrap();
+
+        let copy = home.view_room("Guest Bedroom").unwrap();
+        assert_eq!(copy.name(), "Guest Bedroom");
+        assert!(
+            copy.devices()
+                .eq(home.view_room("Bedroom").unwrap().devices())
         );
+
+        home.update_device("Guest Bedroom", "Light Outlet", |device| {
+            device.try_as_outlet_mut().unwrap().turn_on();
+        })
+        .unwrap();
         assert_eq!(
-            home.view_room("Bedroom")
-                .unwrap()
-                .view_device("Electron thermometer")
-                .unwrap()
-                .info(),
-            "Thermometer: Electron thermometer - Current Temperature: 22.50°C"
+            home.view_room("Guest Bedroom").unwrap().total_power_usage(),
+            400
         );
+        assert_eq!(home.view_room("Bedroom").unwrap().total_power_usage(), 250);
 
         assert_eq!(
-            home.view_room("Living Room")
-                .unwrap()
-                .view_device("Lighter")
-                .unwrap()
-                .name(),
-            "Lighter"
+            home.clone_room("Attic", "Loft"),
+            Err(RoomAccessError {
+                message: "Room with the name 'Attic' not found in the house 'My Smart Home'"
+                    .to_string()
+            })
         );
         assert_eq!(
-            home.view_room("Living Room")
-                .unwrap()
-                .view_device("Lighter")
-                .unwrap()
-                .info(),
-            "Smart Outlet: Lighter - Current State: On, Power Usage: 100 Watt"
+            home.clone_room("Bedroom", "Kitchen Room"),
+            Err(RoomAccessError {
+                message:
+                    "Room with the name 'Kitchen Room' already exists in the house 'My Smart Home'"
+                        .to_string()
+            })
         );
+        assert_eq!(home.rooms_sorted().len(), 4);
+    }
+
+    #[test]
+    fn smart_home_set_temperature_test() {
+        let mut home = three_room_home();
+        home.set_temperature("Kitchen Room", "Kitchen thermometer", 24.0 as Celsius)
+            .unwrap();
         assert_eq!(
-            home.view_room("Living Room")
-                .unwrap()
-                .view_device("PC")
+            home.view_room("Kitchen Room")
                 .unwrap()
-                .name(),
-            "PC"
+                .thermometer_readings(),
+            vec![("Kitchen thermometer".to_string(), 24.0)]
         );
+
         assert_eq!(
-            home.view_room("Living Room")
-                .unwrap()
-                .view_device("PC")
-                .unwrap()
-                .info(),
-            "Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"
+            home.set_temperature("Kitchen Room", "Oven thermometer", 180.0),
+            Err(DeviceAccessError::DeviceAccess(
+                crate::smart_room::AccessError::device_not_found(
+                    "Oven thermometer",
+                    "Kitchen Room"
+                )
+            ))
         );
+
+        let err = home
+            .set_temperature("Kitchen Room", "Teapot Outlet", 90.0)
+            .unwrap_err();
         assert_eq!(
-            home.view_room("Living Room")
-                .unwrap()
-                .view_device("Electronic thermometer")
-                .unwrap()
-                .name(),
-            "Electronic thermometer"
+            err,
+            DeviceAccessError::DeviceType(DeviceTypeError {
+                expected: DeviceKind::Thermometer,
+                actual: DeviceKind::Outlet,
+            })
         );
         assert_eq!(
-            home.view_room("Living Room")
-                .unwrap()
-                .view_device("Electronic thermometer")
-                .unwrap()
-                .info(),
-            "Thermometer: Electronic thermometer - Current Temperature: 22.50°C"
+            std::io::Error::from(err).kind(),
+            std::io::ErrorKind::InvalidInput
         );
+    }
 
+    #[test]
+    fn smart_home_switch_test() {
+        let mut home = three_room_home();
         assert_eq!(
-            home.view_room("Kitchen Room")
-                .unwrap()
-                .view_device("Refrigerator Outlet")
-                .unwrap()
-                .name(),
-            "Refrigerator Outlet"
+            home.switch("Kitchen Room", "Teapot Outlet"),
+            Ok(OutletState::On)
         );
+        assert_eq!(home.total_power_usage(), 850);
         assert_eq!(
-            home.view_room("Kitchen Room")
-                .unwrap()
-                .view_device("Refrigerator Outlet")
-                .unwrap()
-                .info(),
-            "Smart Outlet: Refrigerator Outlet - Current State: On, Power Usage: 100 Watt"
+            home.switch("Kitchen Room", "Teapot Outlet"),
+            Ok(OutletState::Off)
         );
+
         assert_eq!(
-            home.view_room("Kitchen Room")
-                .unwrap()
-                .view_device("Teapot Outlet")
-                .unwrap()
-                .name(),
-            "Teapot Outlet"
+            home.switch("Garage", "Teapot Outlet"),
+            Err(DeviceAccessError::RoomAccess(RoomAccessError {
+                message: "Room with the name 'Garage' not found in the house 'My Smart Home'"
+                    .to_string()
+            }))
         );
         assert_eq!(
-            home.view_room("Kitchen Room")
-                .unwrap()
-                .view_device("Teapot Outlet")
-                .unwrap()
-                .info(),
-            "Smart Outlet: Teapot Outlet - Current State: Off, Power Usage: 0 Watt"
+            home.switch("Kitchen Room", "Kitchen thermometer"),
+            Err(DeviceAccessError::DeviceType(DeviceTypeError {
+                expected: DeviceKind::Outlet,
+                actual: DeviceKind::Thermometer,
+            }))
         );
+    }
+
+    #[test]
+    fn smart_home_power_delta_test() {
+        let before = three_room_home();
+        let mut after = before.clone();
+        after
+            .update_device("Living Room", "PC", |device| {
+                device.try_as_outlet_mut().unwrap().turn_off();
+            })
+            .unwrap();
+
+        assert_eq!(after.power_delta(&before), -250);
+        assert_eq!(before.power_delta(&after), 250);
+        assert_eq!(before.power_delta(&before), 0);
+    }
+
+    #[test]
+    fn smart_home_room_entry_test() {
+        let mut home = three_room_home();
+        home.enable_event_log();
+        let receiver = home.subscribe();
+
+        home.room_entry("Garage".to_string())
+            .or_insert_with(|| SmartRoom::new(String::new(), HashMap::new()))
+            .add_device(
+                "Charger".to_string(),
+                Device::new_outlet("Charger".to_string(), OutletState::On, 300 as Watt),
+            );
+        let garage = home.view_room("Garage").unwrap();
+        assert_eq!(garage.name(), "Garage");
+        assert_eq!(garage.total_power_usage(), 300);
+        assert_eq!(home.events(), &[HomeEvent::RoomAdded("Garage".to_string())]);
+
+        let mut modified = false;
+        let bedroom = home
+            .room_entry("Bedroom".to_string())
+            .and_modify(|_| modified = true)
+            .or_insert_with(|| panic!("Bedroom already exists"));
+        assert_eq!(bedroom.name(), "Bedroom");
+        assert!(modified);
         assert_eq!(
-            home.view_room("Kitchen Room")
-                .unwrap()
-                .view_device("Kitchen thermometer")
-                .unwrap()
-                .name(),
-            "Kitchen thermometer"
+            home.events(),
+            &[
+                HomeEvent::RoomAdded("Garage".to_string()),
+                HomeEvent::RoomChanged("Bedroom".to_string()),
+            ]
         );
         assert_eq!(
-            home.view_room("Kitchen Room")
-                .unwrap()
-                .view_device("Kitchen thermometer")
-                .unwrap()
-                .info(),
-            "Thermometer: Kitchen thermometer - Current Temperature: 20.00°C"
+            receiver.try_iter().collect::<Vec<HomeEvent>>(),
+            home.events()
         );
 
-        let expected = r#"Smart Home: My Smart Home:
- Total Rooms: 3
+        home.room_entry("Attic".to_string()).and_modify(|_| {});
+        assert_eq!(home.events().len(), 2);
+    }
 
-Room[0]:
-Smart Room: Bedroom:
- Total devices: 3
-  [0]: Smart Outlet: Attached Outlet - Current State: On, Power Usage: 250 Watt
-  --------------------------------------
-  [1]: Thermometer: Electron thermometer - Current Temperature: 22.50°C
-  --------------------------------------
-  [2]: Smart Outlet: Light Outlet - Current State: Off, Power Usage: 0 Watt
-=====================================
-Room[1]:
-Smart Room: Kitchen Room:
- Total devices: 3
-  [0]: Thermometer: Kitchen thermometer - Current Temperature: 20.00°C
-  --------------------------------------
-  [1]: Smart Outlet: Refrigerator Outlet - Current State: On, Power Usage: 100 Watt
-  --------------------------------------
-  [2]: Smart Outlet: Teapot Outlet - Current State: Off, Power Usage: 0 Watt
-=====================================
-Room[2]:
-Smart Room: Living Room:
- Total devices: 3
-  [0]: Thermometer: Electronic thermometer - Current Temperature: 22.50°C
-  --------------------------------------
-  [1]: Smart Outlet: Lighter - Current State: On, Power Usage: 100 Watt
-  --------------------------------------
-  [2]: Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"#;
+    #[test]
+    fn error_display_prefix_test() {
+        let mut home = three_room_home();
+        let room_error = home.device("Attic", "Lamp").unwrap_err();
+        let device_error = home.device("Bedroom", "PC").unwrap_err();
+        let type_error = home.switch("Bedroom", "Electron thermometer").unwrap_err();
 
-        assert_eq!(home.info(), expected);
+        for error in [&room_error, &device_error, &type_error] {
+            assert!(error.to_string().starts_with("smart-home error: "));
+            assert!(!error.to_string().contains("Error"));
+        }
+        match &device_error {
+            DeviceAccessError::DeviceAccess(inner) => {
+                assert_eq!(device_error.to_string(), inner.to_string())
+            }
+            other => panic!("unexpected error {other:?}"),
+        }
+        assert_eq!(
+            room_error.to_string(),
+            "smart-home error: Room with the name 'Attic' not found in the house 'My Smart Home'"
+        );
+        assert_eq!(
+            type_error.to_string(),
+            "smart-home error: Expected Outlet device, found Thermometer"
+        );
     }
 
     #[test]
-    fn smart_home_add_rooms_test() {
-        let mut home = SmartHome::new("My Home".to_string(), HashMap::new());
-        let bedroom = SmartRoom::new("Bedroom".to_string(), HashMap::new());
-        home.add_room(bedroom);
-        assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
+    fn smart_home_outlets_in_state_test() {
+        let home = three_room_home();
+        let pair = |room: &str, device: &str| (room.to_string(), device.to_string());
+        assert_eq!(
+            home.outlets_in_state(OutletState::On),
+            vec![
+                pair("Bedroom", "Attached Outlet"),
+                pair("Kitchen Room", "Refrigerator Outlet"),
+                pair("Living Room", "Lighter"),
+                pair("Living Room", "PC"),
+            ]
+        );
+        assert_eq!(
+            home.outlets_in_state(OutletState::Off),
+            vec![
+                pair("Bedroom", "Light Outlet"),
+                pair("Kitchen Room", "Teapot Outlet"),
+            ]
+        );
+    }
 
-        let living_room = SmartRoom::new("Living Room".to_string(), HashMap::new());
-        home.add_room(living_room);
-        assert_eq!(home.view_room("Living Room").unwrap().name(), "Living Room");
+    #[test]
+    fn smart_home_shrink_to_fit_test() {
+        let mut home = SmartHome::new("Hotel".to_string(), HashMap::new());
+        for i in 0..32 {
+            home.add_room(SmartRoom::new(format!("Room {}", i), HashMap::new()));
+        }
+        let full_capacity = home.capacity();
+        assert_eq!(home.prune_empty_rooms().len(), 32);
 
-        assert_eq!(home.rooms.len(), 2);
+        home.shrink_to_fit();
+        assert!(home.capacity() < full_capacity);
     }
 
     #[test]
-    fn smart_home_remove_rooms_test() {
-        let mut home = SmartHome::new("My Home".to_string(), HashMap::new());
-        let bedroom = SmartRoom::new("Bedroom".to_string(), HashMap::new());
-        home.add_room(bedroom);
-        assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
-
-        home.remove_room("Bedroom");
-        assert!(home.view_room("Bedroom").is_none());
+    fn smart_home_energy_cost_rounded_test() {
+        let home = create_home!(
+            "Flat",
+            {
+                "Office",
+                create_room!(
+                    "Office",
+                    "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 305 as Watt)
+                )
+            }
+        );
+        assert_eq!(home.energy_cost(1.0, 1.0), 0.305);
+        assert_eq!(home.energy_cost_rounded(1.0, 1.0, 2), 0.31);
+        assert_eq!(home.energy_cost_rounded(1.0, 1.0, 1), 0.3);
+        assert_eq!(home.energy_cost_rounded(1.0, 1.0, 0), 0.0);
+        assert_eq!(home.energy_cost_rounded(1.0, 2.0, 2), 0.61);
 
-        assert_eq!(home.rooms.len(), 0);
+        assert_eq!(three_room_home().energy_cost_rounded(0.25, 3.0, 2), 0.53);
     }
 
     #[test]
-    fn smart_home_access_room_test() {
-        let bedroom = SmartRoom::new("Bedroom".to_string(), HashMap::new());
-        let living_room = SmartRoom::new("Living Room".to_string(), HashMap::new());
-        let home = SmartHome::new(
-            "My Home".to_string(),
-            HashMap::from([
-                ("Bedroom".to_string(), bedroom),
-                ("Living Room".to_string(), living_room),
-            ]),
+    fn smart_home_energy_cost_rounded_edge_cases_test() {
+        let home = create_home!(
+            "Flat",
+            {
+                "Office",
+                create_room!(
+                    "Office",
+                    "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 305 as Watt)
+                )
+            }
+        );
+        assert_eq!(home.energy_cost(0.9999999, 1.0), 0.3049999695);
+        assert_eq!(home.energy_cost_rounded(0.9999999, 1.0, 2), 0.3);
+        assert_eq!(home.energy_cost_rounded(-1.0, 1.0, 2), -0.31);
+        assert_eq!(home.energy_cost_rounded(1.0, 0.01, 2), 0.0);
+        assert_eq!(
+            home.energy_cost_rounded(1.0, 1.0 / 3.0, 15),
+            0.101666666666667
         );
+        assert_eq!(home.energy_cost_rounded(1e20, 1.0, 2), 3.05e19);
 
-        assert!(home.access_room("Bedroom").is_ok());
-        assert!(home.access_room("Living Room").is_ok());
-        assert!(home.access_room("Kitchen").is_err());
+        assert_eq!(home.energy_cost_rounded(1.0, 1.0, 16), 0.305);
+        assert_eq!(home.energy_cost_rounded(1.0, 1.0, 400), 0.305);
+        assert_eq!(home.energy_cost_rounded(1.0, 1.0, u32::MAX), 0.305);
+        assert_eq!(home.energy_cost_rounded(1.0, 0.0, 400), 0.0);
+        assert!(home.energy_cost_rounded(f64::NAN, 1.0, 2).is_nan());
+    }
 
+    #[test]
+    fn smart_home_state_histogram_test() {
+        let mut home = three_room_home();
         assert_eq!(
-            home.access_room("Kitchen").unwrap_err(),
-            RoomAccessError {
-                message: "Room with the name 'Kitchen' not found in the house 'My Home'"
-                    .to_string()
+            home.state_histogram(),
+            StateHistogram {
+                outlets_on: 4,
+                outlets_off: 2,
+                thermometers: 3,
             }
         );
+
+        home.switch("Kitchen Room", "Teapot Outlet").unwrap();
+        let histogram = home.state_histogram();
+        assert_eq!(histogram.outlets_on, 5);
+        assert_eq!(histogram.outlets_off, 1);
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn smart_home_access_device_test() {
-        let bedroom = SmartRoom::new(
-            "Bedroom".to_string(),
-            HashMap::from([(
-                "Attached Outlet".to_string(),
-                Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
-            )]),
+    fn smart_home_import_merge_from_json_test() {
+        let mut home = three_room_home();
+        let json = r#"{
+            "name": "Garage config",
+            "rooms": {
+                "Garage": {
+                    "name": "Garage",
+                    "devices": {
+                        "Charger": {
+                            "kind": "outlet",
+                            "name": "Charger",
+                            "state": "On",
+                            "power_usage": 300,
+                            "standby_power": 0,
+                            "on_seconds": 0.0,
+                            "expected_power": null
+                        }
+                    }
+                }
+            }
+        }"#;
+        let receiver = home.subscribe();
+        home.import_merge_from_json(json, MergeStrategy::KeepExisting)
+            .unwrap();
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(HomeEvent::RoomAdded("Garage".to_string()))
         );
-        let home = SmartHome::new(
-            "My Home".to_string(),
-            HashMap::from([("Bedroom".to_string(), bedroom)]),
+        assert_eq!(home.name(), "My Smart Home");
+        assert_eq!(home.rooms_sorted().len(), 4);
+        assert_eq!(home.device("Garage", "Charger").unwrap().power_usage(), 300);
+        assert_eq!(home.total_power_usage(), 1000);
+
+        let error = home
+            .import_merge_from_json("{\"name\": 1}", MergeStrategy::Overwrite)
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("smart-home error: Invalid home JSON: ")
         );
+        assert_eq!(home.rooms_sorted().len(), 4);
+        assert!(receiver.try_recv().is_err());
+    }
 
-        assert!(home.device("Bedroom", "Attached Outlet").is_ok());
-        assert!(home.device("Bedroom", "Nonexistent Device").is_err());
+    #[test]
+    fn smart_home_power_bar_chart_test() {
+        let mut home = three_room_home();
+        home.add_room(create_room!(
+            "Attic",
+            "Lamp" => Device::new_outlet("Lamp".to_string(), OutletState::Off, 40 as Watt)
+        ));
         assert_eq!(
-            home.device("Bedroom", "Nonexistent Device").unwrap_err(),
-            DeviceAccessError::DeviceAccess(crate::smart_room::AccessError {
-                message:
-                    "Device with the name 'Nonexistent Device' not found in the room 'Bedroom'"
-                        .to_string()
-            })
+            home.power_bar_chart(10),
+            [
+                "Attic        |          | 0W",
+                "Bedroom      |#######   | 250W",
+                "Kitchen Room |###       | 100W",
+                "Living Room  |##########| 350W",
+            ]
+            .join("\n")
         );
+
+        let empty = SmartHome::new("Empty".to_string(), HashMap::new());
+        assert_eq!(empty.power_bar_chart(10), "");
     }
 
     #[test]
-    fn smart_room_get_ne_exist_room_test() {
-        let home = SmartHome::new("My Home".to_string(), HashMap::new());
-        let none = home.view_room("Some room");
-        assert!(none.is_none());
+    fn smart_home_replay_test() {
+        let mut home = three_room_home();
+        let events = [
+            (
+                300,
+                Command::SetTemperature {
+                    room: "Bedroom".to_string(),
+                    device: "Electron thermometer".to_string(),
+                    temperature: 19.0,
+                },
+            ),
+            (
+                100,
+                Command::TurnOff {
+                    room: "Living Room".to_string(),
+                    device: "PC".to_string(),
+                },
+            ),
+            (
+                200,
+                Command::Switch {
+                    room: "Kitchen Room".to_string(),
+                    device: "Teapot Outlet".to_string(),
+                },
+            ),
+        ];
+        home.replay(&events).unwrap();
+
+        assert_eq!(home.total_power_usage(), 600);
+        assert_eq!(
+            home.outlets_in_state(OutletState::Off),
+            vec![
+                ("Bedroom".to_string(), "Light Outlet".to_string()),
+                ("Living Room".to_string(), "PC".to_string()),
+            ]
+        );
+        assert_eq!(
+            home.view_room("Bedroom").unwrap().thermometer_readings(),
+            vec![("Electron thermometer".to_string(), 19.0)]
+        );
+
+        let failing = [
+            (
+                2,
+                Command::TurnOn {
+                    room: "Living Room".to_string(),
+                    device: "PC".to_string(),
+                },
+            ),
+            (
+                1,
+                Command::TurnOn {
+                    room: "Bedroom".to_string(),
+                    device: "Electron thermometer".to_string(),
+                },
+            ),
+        ];
+        assert!(matches!(
+            home.replay(&failing),
+            Err(DeviceAccessError::DeviceType(_))
+        ));
+        assert_eq!(home.total_power_usage(), 600);
     }
 
     #[test]
-    fn smart_home_switch_outlet_test() {
-        let bedroom = create_room!(
-            "Bedroom",
-            "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
-            "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
-            "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius)
+    fn smart_home_load_factor_test() {
+        let mut home = create_home!(
+            "Flat",
+            {
+                "Office",
+                create_room!(
+                    "Office",
+                    "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 200 as Watt),
+                    "Printer" => Device::new_outlet("Printer".to_string(), OutletState::Off, 200 as Watt),
+                    "Thermometer" => Device::new_thermometer("Thermometer".to_string(), 21.0 as Celsius)
+                )
+            }
         );
-        let living_room = create_room!(
-            "Living Room",
-            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
-            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
-            "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+        assert_eq!(home.total_power_rating(), 400);
+        assert_eq!(home.load_factor(), 0.5);
+
+        home.switch("Office", "Printer").unwrap();
+        assert_eq!(home.load_factor(), 1.0);
+
+        assert_eq!(three_room_home().load_factor(), 0.7);
+        let empty = SmartHome::new("Empty".to_string(), HashMap::new());
+        assert_eq!(empty.load_factor(), 0.0);
+    }
+
+    #[test]
+    fn smart_home_walk_test() {
+        #[derive(Default)]
+        struct PathCollector {
+            paths: Vec<String>,
+        }
+
+        impl HomeVisitor for PathCollector {
+            fn enter_room(&mut self, name: &str) {
+                self.paths.push(format!("{}/", name));
+            }
+
+            fn visit_device(&mut self, room: &str, name: &str, _device: &Device) {
+                self.paths.push(format!("{}/{}", room, name));
+            }
+        }
+
+        let home = three_room_home();
+        let mut collector = PathCollector::default();
+        home.walk(&mut collector);
+        assert_eq!(
+            collector.paths,
+            vec![
+                "Bedroom/",
+                "Bedroom/Attached Outlet",
+                "Bedroom/Electron thermometer",
+                "Bedroom/Light Outlet",
+                "Kitchen Room/",
+                "Kitchen Room/Kitchen thermometer",
+                "Kitchen Room/Refrigerator Outlet",
+                "Kitchen Room/Teapot Outlet",
+                "Living Room/",
+                "Living Room/Electronic thermometer",
+                "Living Room/Lighter",
+                "Living Room/PC",
+            ]
         );
-        let mut home = create_home!(
-            "My Home",
-            {"Bedroom", bedroom},
-            {"Living Room", living_room}
+    }
+
+    #[test]
+    fn device_access_error_predicates_test() {
+        let room_error = DeviceAccessError::RoomAccess(RoomAccessError {
+            message: "Room with the name 'Attic' not found in the house 'My Smart Home'"
+                .to_string(),
+        });
+        assert!(room_error.is_room_error());
+        assert!(!room_error.is_device_error());
+
+        let device_error = DeviceAccessError::DeviceAccess(
+            crate::smart_room::AccessError::device_not_found("PC", "Bedroom"),
         );
+        assert!(device_error.is_device_error());
+        assert!(!device_error.is_room_error());
+
+        let type_error = DeviceAccessError::DeviceType(DeviceTypeError {
+            expected: DeviceKind::Outlet,
+            actual: DeviceKind::Thermometer,
+        });
+        assert!(type_error.is_device_error());
+        assert!(!type_error.is_room_error());
+    }
 
+    #[test]
+    fn smart_home_from_rooms_test() {
+        let rooms = vec![
+            create_room!(
+                "Bedroom",
+                "Lamp" => Device::new_outlet("Lamp".to_string(), OutletState::On, 40 as Watt)
+            ),
+            create_room!(
+                "Office",
+                "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt)
+            ),
+        ];
+        let home = SmartHome::from_rooms("Flat".to_string(), rooms);
+        assert_eq!(home.name(), "Flat");
+        assert_eq!(home.rooms_sorted().len(), 2);
+        assert_eq!(home.device("Office", "PC").unwrap().power_usage(), 250);
         assert_eq!(home.view_room("Bedroom").unwrap().name(), "Bedroom");
-        assert_eq!(home.view_room("Living Room").unwrap().name(), "Living Room");
+        assert!(home.view_room("Kitchen").is_none());
+    }
 
+    #[test]
+    fn smart_home_rooms_over_test() {
+        let home = three_room_home();
+        assert_eq!(home.rooms_over(300), vec!["Living Room"]);
+        assert_eq!(home.rooms_over(250), vec!["Living Room"]);
         assert_eq!(
-            home.view_room("Bedroom")
-                .unwrap()
-                .view_device("Attached Outlet")
-                .unwrap()
-                .info(),
-            "Smart Outlet: Attached Outlet - Current State: On, Power Usage: 250 Watt"
+            home.rooms_over(99),
+            vec!["Bedroom", "Kitchen Room", "Living Room"]
         );
+        assert!(home.rooms_over(350).is_empty());
+    }
 
-        {
-            let device = home
-                .get_room("Bedroom")
-                .unwrap()
-                .get_device("Attached Outlet")
-                .unwrap();
-            let outlet = match device {
-                Device::OutletType(o) => o,
-                _ => panic!("Expected OutletType"),
-            };
-            assert_eq!(outlet.state(), OutletState::On);
-            outlet.switch();
-            assert_eq!(outlet.state(), OutletState::Off);
-        }
+    #[test]
+    fn smart_home_snapshot_test() {
+        let mut home = three_room_home();
+        let snapshot = home.snapshot();
         assert_eq!(
-            home.view_room("Bedroom")
-                .unwrap()
-                .view_device("Attached Outlet")
-                .unwrap()
-                .info(),
-            "Smart Outlet: Attached Outlet - Current State: Off, Power Usage: 0 Watt"
+            snapshot,
+            HomeSnapshot {
+                room_count: 3,
+                device_count: 9,
+                total_power: 700,
+                power_per_room: BTreeMap::from([
+                    ("Bedroom".to_string(), 250),
+                    ("Kitchen Room".to_string(), 100),
+                    ("Living Room".to_string(), 350),
+                ]),
+            }
         );
+        assert_eq!(snapshot.total_power, home.total_power_usage());
+        assert_eq!(snapshot.device_count, home.iter_devices().count());
+
+        home.switch("Kitchen Room", "Teapot Outlet").unwrap();
+        assert_eq!(snapshot.total_power, 700);
+        assert_eq!(home.snapshot().total_power, 850);
     }
+
     #[test]
-    fn smart_home_changed_info_test() {
-        let mut home = create_home!(
-            "My Smart Home",
-            {
-                "Bedroom",
-                create_room!(
-                    "Bedroom",
-                    "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
-                    "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
-                    "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius)
-                )
-            },
+    fn smart_home_to_markdown_test() {
+        let home = create_home!(
+            "My Flat",
             {
-                "Living Room",
+                "Office",
                 create_room!(
-                    "Living Room",
-                    "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+                    "Office",
                     "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
-                    "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+                    "Lamp | Desk" => Device::new_outlet("Lamp | Desk".to_string(), OutletState::Off, 40 as Watt),
+                    "Thermometer" => Device::new_thermometer("Thermometer".to_string(), 21.5 as Celsius)
                 )
             },
             {
-                "Kitchen Room",
-                create_room!(
-                    "Kitchen Room",
-                    "Refrigerator Outlet" => Device::new_outlet("Refrigerator Outlet".to_string(), OutletState::On, 100 as Watt),
-                    "Teapot Outlet" => Device::new_outlet("Teapot Outlet".to_string(), OutletState::Off, 150 as Watt),
-                    "Kitchen thermometer" => Device::new_thermometer("Kitchen thermometer".to_string(), 20.0 as Celsius)
-                )
+                "Attic",
+                create_room!("Attic",)
             }
         );
+        assert_eq!(
+            home.to_markdown(),
+            [
+                "# My Flat",
+                "",
+                "## Attic",
+                "",
+                "| Device | Kind | State | Power |",
+                "| --- | --- | --- | --- |",
+                "",
+                "## Office",
+                "",
+                "| Device | Kind | State | Power |",
+                "| --- | --- | --- | --- |",
+                "| Lamp \\| Desk | Outlet | Off | 0 W |",
+                "| PC | Outlet | On | 250 W |",
+                "| Thermometer | Thermometer | 21.5°C | - |",
+            ]
+            .join("\n")
+        );
+    }
 
-        let expected = r#"Smart Home: My Smart Home:
- Total Rooms: 3
+    #[test]
+    fn smart_home_view_room_ci_test() {
+        let mut home = three_room_home();
+        assert_eq!(home.view_room_ci("bedroom").unwrap().name(), "Bedroom");
+        assert_eq!(
+            home.view_room_ci("KITCHEN room").unwrap().name(),
+            "Kitchen Room"
+        );
+        assert!(home.view_room_ci("garage").is_none());
 
-Room[0]:
-Smart Room: Bedroom:
- Total devices: 3
-  [0]: Smart Outlet: Attached Outlet - Current State: On, Power Usage: 250 Watt
-  --------------------------------------
-  [1]: Thermometer: Electron thermometer - Current Temperature: 22.50°C
-  --------------------------------------
-  [2]: Smart Outlet: Light Outlet - Current State: Off, Power Usage: 0 Watt
-=====================================
-Room[1]:
-Smart Room: Kitchen Room:
- Total devices: 3
-  [0]: Thermometer: Kitchen thermometer - Current Temperature: 20.00°C
-  --------------------------------------
-  [1]: Smart Outlet: Refrigerator Outlet - Current State: On, Power Usage: 100 Watt
-  --------------------------------------
-  [2]: Smart Outlet: Teapot Outlet - Current State: Off, Power Usage: 0 Watt
-=====================================
-Room[2]:
-Smart Room: Living Room:
- Total devices: 3
-  [0]: Thermometer: Electronic thermometer - Current Temperature: 22.50°C
-  --------------------------------------
-  [1]: Smart Outlet: Lighter - Current State: On, Power Usage: 100 Watt
-  --------------------------------------
-  [2]: Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"#;
+        home.add_room(SmartRoom::new("bedroom".to_string(), HashMap::new()));
+        assert_eq!(home.view_room_ci("BEDROOM").unwrap().name(), "Bedroom");
+        assert_eq!(home.view_room("bedroom").unwrap().name(), "bedroom");
+        assert!(home.view_room("living room").is_none());
+    }
 
-        assert_eq!(home.info(), expected);
+    #[test]
+    fn smart_home_power_distribution_test() {
+        let mut home = three_room_home();
+        home.switch("Living Room", "Lighter").unwrap();
+        home.switch("Kitchen Room", "Refrigerator Outlet").unwrap();
+        assert_eq!(home.total_power_usage(), 500);
 
-        {
-            let kitchen_room = home.get_room("Kitchen Room").unwrap();
-            let device = kitchen_room.get_device("Teapot Outlet").unwrap();
-            let outlet = match device {
-                Device::OutletType(outlet) => outlet,
-                _ => panic!("Expected OutletType"),
-            };
-            assert_eq!(outlet.state(), OutletState::Off);
-            outlet.switch();
-            assert_eq!(outlet.state(), OutletState::On);
-        }
+        let distribution = home.power_distribution();
+        assert_eq!(
+            distribution,
+            vec![
+                ("Bedroom".to_string(), 0.5),
+                ("Kitchen Room".to_string(), 0.0),
+                ("Living Room".to_string(), 0.5),
+            ]
+        );
+        let total: f64 = distribution.iter().map(|(_, share)| share).sum();
+        assert!((total - 1.0).abs() < 1e-9);
 
-        let expected = r#"Smart Home: My Smart Home:
- Total Rooms: 3
+        home.switch("Bedroom", "Attached Outlet").unwrap();
+        home.switch("Living Room", "PC").unwrap();
+        assert!(
+            home.power_distribution()
+                .iter()
+                .all(|(_, share)| *share == 0.0)
+        );
+        assert!(
+            SmartHome::new("Empty".to_string(), HashMap::new())
+                .power_distribution()
+                .is_empty()
+        );
+    }
 
-Room[0]:
-Smart Room: Bedroom:
- Total devices: 3
-  [0]: Smart Outlet: Attached Outlet - Current State: On, Power Usage: 250 Watt
-  --------------------------------------
-  [1]: Thermometer: Electron thermometer - Current Temperature: 22.50°C
-  --------------------------------------
-  [2]: Smart Outlet: Light Outlet - Current State: Off, Power Usage: 0 Watt
-=====================================
-Room[1]:
-Smart Room: Kitchen Room:
- Total devices: 3
-  [0]: Thermometer: Kitchen thermometer - Current Temperature: 20.00°C
-  --------------------------------------
-  [1]: Smart Outlet: Refrigerator Outlet - Current State: On, Power Usage: 100 Watt
-  --------------------------------------
-  [2]: Smart Outlet: Teapot Outlet - Current State: On, Power Usage: 150 Watt
-=====================================
-Room[2]:
-Smart Room: Living Room:
- Total devices: 3
-  [0]: Thermometer: Electronic thermometer - Current Temperature: 22.50°C
-  --------------------------------------
-  [1]: Smart Outlet: Lighter - Current State: On, Power Usage: 100 Watt
-  --------------------------------------
-  [2]: Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"#;
+    #[test]
+    fn smart_home_batch_switch_test() {
+        let mut home = three_room_home();
+        let results = home.batch_switch(&[
+            ("Kitchen Room", "Teapot Outlet"),
+            ("Garage", "Drill"),
+            ("Bedroom", "Attached Outlet"),
+        ]);
+        assert_eq!(
+            results,
+            vec![
+                Ok(OutletState::On),
+                Err(DeviceAccessError::RoomAccess(RoomAccessError {
+                    message: "Room with the name 'Garage' not found in the house 'My Smart Home'"
+                        .to_string()
+                })),
+                Ok(OutletState::Off),
+            ]
+        );
+        assert_eq!(home.total_power_usage(), 600);
+        assert!(home.batch_switch(&[]).is_empty());
+    }
 
-        assert_eq!(home.info(), expected);
+    #[test]
+    fn smart_home_fork_test() {
+        let home = three_room_home();
+        let mut fork = home.fork();
+        assert_eq!(fork, home);
+
+        fork.switch("Bedroom", "Attached Outlet").unwrap();
+        fork.set_temperature("Kitchen Room", "Kitchen thermometer", 25.0)
+            .unwrap();
+        fork.remove_room("Living Room");
+
+        assert_ne!(fork, home);
+        assert_eq!(home, three_room_home());
+        assert_eq!(home.total_power_usage(), 700);
+        assert!(home.view_room("Living Room").is_some());
+    }
+
+    #[test]
+    fn smart_home_fold_rooms_test() {
+        let home = three_room_home();
+        let total = home.fold_rooms(0 as Watt, |total, room| total + room.total_power_usage());
+        assert_eq!(total, home.total_power_usage());
+
+        let names = home.fold_rooms(String::new(), |mut names, room| {
+            if !names.is_empty() {
+                names.push_str(", ");
+            }
+            names.push_str(&room.name());
+            names
+        });
+        assert_eq!(names, "Bedroom, Kitchen Room, Living Room");
+
+        let max_power = home.fold_rooms(0 as Watt, |max, room| max.max(room.total_power_usage()));
+        assert_eq!(max_power, 350);
+    }
+
+    #[test]
+    fn smart_home_validate_power_budgets_test() {
+        let home = three_room_home();
+        let budgets = HashMap::from([
+            ("Living Room".to_string(), 300 as Watt),
+            ("Bedroom".to_string(), 250 as Watt),
+            ("Garage".to_string(), 0 as Watt),
+        ]);
+        assert_eq!(
+            home.validate_power_budgets(&budgets),
+            vec![("Living Room".to_string(), 350, 300)]
+        );
+        assert!(home.validate_power_budgets(&HashMap::new()).is_empty());
     }
 }
@@ -1,47 +1,100 @@
-use crate::smart_devices::Device;
+use crate::json;
+use crate::locale::Lang;
+use crate::smart_devices::{
+    Celsius, Device, Outlet, OutletDevice, OutletState, PowerSum, TemperatureSensor, Thermometer,
+    Watt, eq_ignore_case,
+};
 use crate::traits::Information;
+use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
+use std::fmt;
 use std::fmt::Display;
 use std::string::String;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SmartRoom {
     name: String,
     devices: HashMap<String, Device>,
 }
 
+/// What went wrong when accessing a device of a room.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessErrorKind {
+    /// No device is stored under the key.
+    NotFound,
+    /// The device exists but is not an outlet.
+    NotAnOutlet,
+    /// Several devices would end up under the same key.
+    KeyCollision,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct AccessError {
+    pub kind: AccessErrorKind,
     pub message: String,
 }
 
 impl Display for AccessError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "AccessError: {}", self.message)
+        write!(f, "smart-home error: {}", self.message)
     }
 }
 
 impl Error for AccessError {}
 
+impl AccessError {
+    pub(crate) fn device_not_found(key: &str, room_name: &str) -> Self {
+        AccessError {
+            kind: AccessErrorKind::NotFound,
+            message: format!(
+                "Device with the name '{}' not found in the room '{}'",
+                key, room_name
+            ),
+        }
+    }
+
+    pub(crate) fn not_an_outlet(key: &str, room_name: &str) -> Self {
+        AccessError {
+            kind: AccessErrorKind::NotAnOutlet,
+            message: format!(
+                "Device with the name '{}' in the room '{}' is not an outlet",
+                key, room_name
+            ),
+        }
+    }
+}
+
+/// Defines how key collisions are resolved when merging rooms or homes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep the entry that is already present and drop the incoming one.
+    KeepExisting,
+    /// Replace the entry that is already present with the incoming one.
+    Overwrite,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateKeyError {
+    pub message: String,
+}
+
+impl Display for DuplicateKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "smart-home error: {}", self.message)
+    }
+}
+
+impl Error for DuplicateKeyError {}
+
 impl Information for SmartRoom {
     fn name(&self) -> String {
         self.name.clone()
     }
 
     fn info(&self) -> String {
-        let sorted_devices: BTreeMap<String, Device> = self.clone().devices.into_iter().collect();
-        let enumerated_devices: Vec<String> = sorted_devices
-            .iter()
-            .enumerate()
-            .map(|(i, d)| format!("[{}]: {}", i, d.1.info()))
-            .collect();
-        format!(
-            "\nSmart Room: {}:\n Total devices: {}\n  {}",
-            self.name,
-            enumerated_devices.len(),
-            enumerated_devices.join("\n  --------------------------------------\n  ")
-        )
+        self.info_localized(Lang::En)
     }
 }
 
@@ -60,6 +113,41 @@ impl SmartRoom {
         SmartRoom { name, devices }
     }
 
+    /// Creates a new SmartRoom from `(key, device)` pairs, rejecting duplicate keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the smart room.
+    /// * `devices` - The devices with their identifying keys.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SmartRoom)` - A new SmartRoom instance if every key is unique
+    /// * `Err(DuplicateKeyError)` - An error naming the first repeated key
+    pub fn try_new(
+        name: String,
+        devices: impl IntoIterator<Item = (String, Device)>,
+    ) -> Result<Self, DuplicateKeyError> {
+        let mut unique_devices = HashMap::new();
+        for (key, device) in devices {
+            match unique_devices.entry(key) {
+                Entry::Occupied(entry) => {
+                    return Err(DuplicateKeyError {
+                        message: format!(
+                            "Device key '{}' is used more than once in the room '{}'",
+                            entry.key(),
+                            name
+                        ),
+                    });
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(device);
+                }
+            }
+        }
+        Ok(SmartRoom::new(name, unique_devices))
+    }
+
     /// Returns an immutable reference to the device with the given key.
     ///
     /// # Arguments
@@ -73,6 +161,23 @@ impl SmartRoom {
         self.devices.get(key)
     }
 
+    /// Returns a reference to the device whose key matches `key` ignoring case.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the device, in any case.
+    ///
+    /// # Returns
+    ///
+    /// The first matching device in key order, or `None` if no key matches.
+    pub fn view_device_ci(&self, key: &str) -> Option<&Device> {
+        self.devices
+            .iter()
+            .filter(|(device_key, _)| eq_ignore_case(device_key, key))
+            .min_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, device)| device)
+    }
+
     /// Returns a mutable reference to the device with the given key,
     /// allowing the caller to modify the device.
     ///
@@ -87,6 +192,30 @@ impl SmartRoom {
         self.devices.get_mut(key)
     }
 
+    /// Returns mutable references to several devices at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys of the devices, which must all be different.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the devices in the order of `keys`, or `None` if any key
+    /// is missing or appears more than once.
+    pub fn get_many_mut<const N: usize>(&mut self, keys: [&str; N]) -> Option<[&mut Device; N]> {
+        for (i, key) in keys.iter().enumerate() {
+            if keys[..i].contains(key) {
+                return None;
+            }
+        }
+        let devices: Vec<&mut Device> = self
+            .devices
+            .get_disjoint_mut(keys)
+            .into_iter()
+            .collect::<Option<_>>()?;
+        devices.try_into().ok()
+    }
+
     /// Adds a new device to the room with the specified key.
     /// If a device with the same key already exists, it will be replaced.
     ///
@@ -98,6 +227,75 @@ impl SmartRoom {
         self.devices.insert(key, device);
     }
 
+    /// Adds a device like `add_device`, trimming surrounding whitespace first.
+    ///
+    /// Both the key and the device name are trimmed. `add_device` keeps them as given,
+    /// so existing callers are unaffected.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The unique identifier for the device, possibly padded with whitespace.
+    /// * `device` - The device to be added to the room.
+    pub fn add_device_normalized(&mut self, key: &str, mut device: Device) {
+        if !matches!(device, Device::Empty) {
+            let name = device.name();
+            device.set_name(name.trim().to_string());
+        }
+        self.add_device(key.trim().to_string(), device);
+    }
+
+    /// Switches the outlet stored under the given key to the opposite state.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the outlet.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the new state of the outlet, or an `AccessError` of kind
+    /// `NotFound` if the device is missing and `NotAnOutlet` if it is not an outlet.
+    pub fn toggle_device(&mut self, key: &str) -> Result<OutletState, AccessError> {
+        let room_name = &self.name;
+        let outlet = self
+            .devices
+            .get_mut(key)
+            .ok_or_else(|| AccessError::device_not_found(key, room_name))?
+            .try_as_outlet_mut()
+            .map_err(|_| AccessError::not_an_outlet(key, room_name))?;
+        outlet.switch();
+        Ok(outlet.state())
+    }
+
+    /// Inserts or replaces the device stored under the given key, renaming it to the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to store the device under; also becomes the device name.
+    /// * `device` - The device to store. `Device::Empty` has no name and is stored as is.
+    ///
+    /// # Returns
+    ///
+    /// The device previously stored under the key, or `None` if the key was new.
+    pub fn upsert_device(&mut self, key: String, mut device: Device) -> Option<Device> {
+        device.set_name(key.clone());
+        self.devices.insert(key, device)
+    }
+
+    /// Replaces the device stored under the given key and returns the previous one.
+    /// Unlike `add_device`, the replaced device is handed back to the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the device to be replaced.
+    /// * `new` - The device to store under the key.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the replaced device, or `None` if the key was empty.
+    pub fn swap_device(&mut self, key: &str, new: Device) -> Option<Device> {
+        self.devices.insert(key.to_string(), new)
+    }
+
     /// Removes a device from the room by its key.
     ///
     /// # Arguments
@@ -110,6 +308,460 @@ impl SmartRoom {
     pub fn remove_device(&mut self, key: &str) -> Option<Device> {
         self.devices.remove(key)
     }
+
+    /// Removes every device matching a predicate and hands the removed devices back.
+    ///
+    /// # Arguments
+    ///
+    /// * `pred` - Called with the key and the device; devices for which it returns `true`
+    ///   are removed.
+    ///
+    /// # Returns
+    ///
+    /// The removed `(device_key, device)` pairs, sorted by key.
+    pub fn extract_devices<F: Fn(&str, &Device) -> bool>(
+        &mut self,
+        pred: F,
+    ) -> Vec<(String, Device)> {
+        let mut extracted: Vec<(String, Device)> = self
+            .devices
+            .extract_if(|key, device| pred(key, device))
+            .collect();
+        extracted.sort_by(|(a, _), (b, _)| a.cmp(b));
+        extracted
+    }
+
+    /// Returns an iterator over the devices in the room, sorted by key.
+    ///
+    /// This is the sorted counterpart of `HashMap::iter`.
+    ///
+    /// # Returns
+    ///
+    /// An iterator yielding `(device_key, device)` pairs in key order.
+    pub fn iter_devices(&self) -> impl Iterator<Item = (&str, &Device)> {
+        let sorted_devices: BTreeMap<&str, &Device> =
+            self.devices.iter().map(|(k, d)| (k.as_str(), d)).collect();
+        sorted_devices.into_iter()
+    }
+
+    /// Returns the combined current power draw of all devices in the room.
+    ///
+    /// The sum saturates at `Watt::MAX` instead of overflowing.
+    pub fn total_power_usage(&self) -> Watt {
+        self.devices.values().total_power()
+    }
+
+    /// Returns the combined rated power of all outlets in the room, whether on or off.
+    ///
+    /// Unlike `total_power_usage`, this is what the room would draw with every outlet on.
+    /// The sum saturates at `Watt::MAX` instead of overflowing.
+    pub fn total_power_rating(&self) -> Watt {
+        self.devices.values().fold(0, |total: Watt, device| {
+            total.saturating_add(device.power_rating())
+        })
+    }
+
+    /// Sheds load by switching off outlets until the room fits the power cap.
+    ///
+    /// Outlets are switched off starting from the largest current draw;
+    /// outlets with equal draw are switched off in key order.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_watts` - The maximum total power usage allowed in the room.
+    ///
+    /// # Returns
+    ///
+    /// The keys of the outlets that were switched off, in the order they were switched off.
+    pub fn enforce_power_cap(&mut self, max_watts: Watt) -> Vec<String> {
+        let mut total = self.total_power_usage();
+        let mut candidates: Vec<(&String, &mut Device)> = self
+            .devices
+            .iter_mut()
+            .filter(|(_, device)| {
+                matches!(device, Device::OutletType(outlet) if outlet.state() == OutletState::On)
+            })
+            .collect();
+        candidates.sort_by(|a, b| {
+            b.1.power_usage()
+                .cmp(&a.1.power_usage())
+                .then_with(|| a.0.cmp(b.0))
+        });
+
+        let mut switched_off = Vec::new();
+        for (key, device) in candidates {
+            if total <= max_watts {
+                break;
+            }
+            if let Device::OutletType(outlet) = device {
+                let usage = outlet.power_usage();
+                outlet.turn_off();
+                total = total - usage + outlet.power_usage();
+                switched_off.push(key.clone());
+            }
+        }
+        switched_off
+    }
+
+    /// Returns the room report with labels in the given language.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The language of the report labels.
+    ///
+    /// # Returns
+    ///
+    /// The same report as `info()`, with every label translated.
+    pub fn info_localized(&self, lang: Lang) -> String {
+        let mut info = String::new();
+        self.write_info_localized(&mut info, lang)
+            .expect("writing to a String cannot fail");
+        info
+    }
+
+    /// Writes the room report into any `fmt::Write` sink without building it in memory first.
+    ///
+    /// # Arguments
+    ///
+    /// * `w` - The sink receiving the same text as `info()`.
+    pub fn write_info<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        self.write_info_localized(w, Lang::En)
+    }
+
+    /// Writes the room report with labels in the given language into a `fmt::Write` sink.
+    ///
+    /// # Arguments
+    ///
+    /// * `w` - The sink receiving the same text as `info_localized(lang)`.
+    /// * `lang` - The language of the report labels.
+    pub fn write_info_localized<W: fmt::Write>(&self, w: &mut W, lang: Lang) -> fmt::Result {
+        let labels = lang.labels();
+        write!(
+            w,
+            "\n{}: {}:\n {}: {}\n  ",
+            labels.smart_room,
+            self.name,
+            labels.total_devices,
+            self.devices.len()
+        )?;
+        for (i, (_, device)) in self.iter_devices().enumerate() {
+            if i > 0 {
+                w.write_str("\n  --------------------------------------\n  ")?;
+            }
+            write!(w, "[{}]: {}", i, device.info_localized(lang))?;
+        }
+        Ok(())
+    }
+
+    /// Returns one report line per device, sorted by key, without header or separators.
+    ///
+    /// Each line matches the device entry of `info()`, e.g. `"[0]: Smart Outlet: ..."`.
+    pub fn info_lines(&self) -> Vec<String> {
+        self.iter_devices()
+            .enumerate()
+            .map(|(i, (_, device))| format!("[{}]: {}", i, device.info()))
+            .collect()
+    }
+
+    /// Changes the name of the room.
+    ///
+    /// A room stored inside a `SmartHome` is keyed by its name, and this method
+    /// does not update that key. Use `SmartHome::rename_room` for rooms in a home.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The new name of the room.
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    /// Renames every device by applying a transformation to its key.
+    /// The inner device names are updated to match the new keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The transformation producing a new key from the current one.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If every device was renamed
+    /// * `Err(AccessError)` - If two devices would end up under the same key;
+    ///   the room is left unchanged in that case
+    pub fn rename_devices_with<F: Fn(&str) -> String>(&mut self, f: F) -> Result<(), AccessError> {
+        let mut renamed: HashMap<String, &str> = HashMap::with_capacity(self.devices.len());
+        for key in self.devices.keys() {
+            let new_key = f(key);
+            if let Some(other) = renamed.insert(new_key.clone(), key) {
+                return Err(AccessError {
+                    kind: AccessErrorKind::KeyCollision,
+                    message: format!(
+                        "Devices '{}' and '{}' would both be renamed to '{}' in the room '{}'",
+                        other.min(key.as_str()),
+                        other.max(key.as_str()),
+                        new_key,
+                        self.name
+                    ),
+                });
+            }
+        }
+
+        self.devices = std::mem::take(&mut self.devices)
+            .into_iter()
+            .map(|(key, mut device)| {
+                let new_key = f(&key);
+                device.set_name(new_key.clone());
+                (new_key, device)
+            })
+            .collect();
+        Ok(())
+    }
+
+    /// Checks that the room and its devices are consistently named.
+    ///
+    /// The room name must not be empty, and every device (except `Device::Empty`,
+    /// which has no name of its own) must have a non-empty name equal to its key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If no problems were found
+    /// * `Err(Vec<String>)` - A description of every problem, in device key order
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+        if self.name.is_empty() {
+            problems.push("Room has an empty name".to_string());
+        }
+        for (key, device) in self.iter_devices() {
+            if matches!(device, Device::Empty) {
+                continue;
+            }
+            let name = device.name();
+            if name.is_empty() {
+                problems.push(format!(
+                    "Device '{}' in the room '{}' has an empty name",
+                    key, self.name
+                ));
+            } else if name != key {
+                problems.push(format!(
+                    "Device '{}' in the room '{}' is stored under the key '{}'",
+                    name, self.name, key
+                ));
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Serializes the room to JSON without using any external crate.
+    ///
+    /// Devices are written in key order, so the output is stable.
+    ///
+    /// # Returns
+    ///
+    /// A String with a JSON object holding the room `name` and its `devices` by key.
+    pub fn to_json_manual(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+
+    /// Serializes the room to JSON with serde. Only available with the `serde` feature.
+    ///
+    /// The output matches `to_json_manual` apart from key order and whitespace.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the JSON text, or the `serde_json::Error` that stopped it.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    pub(crate) fn write_json(&self, out: &mut String) {
+        out.push('{');
+        json::write_key(out, "name");
+        json::write_string(out, &self.name);
+        out.push(',');
+        json::write_key(out, "devices");
+        out.push('{');
+        for (i, (key, device)) in self.iter_devices().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            json::write_key(out, key);
+            device.write_json(out);
+        }
+        out.push_str("}}");
+    }
+
+    /// Moves the devices matching a predicate into a newly created room.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_room_name` - The name of the room to create.
+    /// * `pred` - A predicate receiving each device key and device.
+    ///
+    /// # Returns
+    ///
+    /// A new SmartRoom holding the matching devices; this room keeps the rest.
+    pub fn split_off<F: Fn(&str, &Device) -> bool>(
+        &mut self,
+        new_room_name: String,
+        pred: F,
+    ) -> SmartRoom {
+        let devices = self
+            .devices
+            .extract_if(|key, device| pred(key, device))
+            .collect();
+        SmartRoom::new(new_room_name, devices)
+    }
+
+    /// Returns an iterator over the devices in the room, sorted by key.
+    pub fn devices(&self) -> impl Iterator<Item = &Device> {
+        self.iter_devices().map(|(_, device)| device)
+    }
+
+    /// Returns the current readings of all thermometers in the room.
+    ///
+    /// # Returns
+    ///
+    /// A Vec of `(device_key, temperature)` pairs sorted by key.
+    pub fn thermometer_readings(&self) -> Vec<(String, Celsius)> {
+        self.iter_devices()
+            .filter_map(|(key, device)| match device {
+                Device::ThermometerType(thermometer) => {
+                    Some((key.to_string(), thermometer.current_temperature()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Splits the devices of the room into outlets and thermometers in a single pass.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the outlets and the thermometers, each sorted by device key.
+    /// Empty slots are left out.
+    pub fn partition_by_kind(&self) -> (Vec<&Outlet>, Vec<&Thermometer>) {
+        let mut outlets = Vec::new();
+        let mut thermometers = Vec::new();
+        for (_, device) in self.iter_devices() {
+            match device {
+                Device::OutletType(outlet) => outlets.push(outlet),
+                Device::ThermometerType(thermometer) => thermometers.push(thermometer),
+                Device::Empty => {}
+            }
+        }
+        (outlets, thermometers)
+    }
+
+    /// Returns the outlets whose power rating lies within `[min, max]`.
+    ///
+    /// The rating is used regardless of the outlet state.
+    ///
+    /// # Returns
+    ///
+    /// The matching outlets sorted by name. Empty if `min` is greater than `max`.
+    pub fn outlets_in_range(&self, min: Watt, max: Watt) -> Vec<&Outlet> {
+        let mut outlets: Vec<&Outlet> = self
+            .devices
+            .values()
+            .filter_map(|device| match device {
+                Device::OutletType(outlet) if (min..=max).contains(&outlet.power_rating()) => {
+                    Some(outlet)
+                }
+                _ => None,
+            })
+            .collect();
+        outlets.sort_by_key(|outlet| outlet.name());
+        outlets
+    }
+
+    /// Finds the switched-on outlet with the highest current power draw.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the device key and power usage, or `None` if no outlet is on.
+    /// Ties are resolved in favour of the first key in name order.
+    pub fn busiest_outlet(&self) -> Option<(&str, Watt)> {
+        self.devices
+            .iter()
+            .filter_map(|(key, device)| match device {
+                Device::OutletType(outlet) if outlet.state() == OutletState::On => {
+                    Some((key.as_str(), outlet.power_usage()))
+                }
+                _ => None,
+            })
+            .min_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)))
+    }
+
+    /// Returns the current power draw of every outlet in the room.
+    ///
+    /// # Returns
+    ///
+    /// A BTreeMap from device keys to power usage. Thermometers and empty slots are excluded.
+    pub fn power_map(&self) -> BTreeMap<String, Watt> {
+        self.devices
+            .iter()
+            .filter_map(|(key, device)| match device {
+                Device::OutletType(outlet) => Some((key.clone(), outlet.power_usage())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over mutable references to the devices in the room.
+    /// The iteration order is unspecified.
+    pub fn devices_mut(&mut self) -> impl Iterator<Item = &mut Device> {
+        self.devices.values_mut()
+    }
+
+    /// Moves all devices of another room into this one.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The room whose devices are moved; its name is discarded.
+    /// * `strategy` - How to resolve devices stored under the same key in both rooms.
+    pub fn merge_from(&mut self, other: SmartRoom, strategy: MergeStrategy) {
+        for (key, device) in other.devices {
+            match strategy {
+                MergeStrategy::KeepExisting => {
+                    self.devices.entry(key).or_insert(device);
+                }
+                MergeStrategy::Overwrite => {
+                    self.devices.insert(key, device);
+                }
+            }
+        }
+    }
+
+    /// Returns the number of devices in the room.
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    /// Returns `true` if the room has no devices.
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    /// Returns the number of devices the room can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.devices.capacity()
+    }
+
+    /// Releases the memory the room kept after devices were removed.
+    pub fn shrink_to_fit(&mut self) {
+        self.devices.shrink_to_fit();
+    }
+
+    /// Splits the room into its name and device map.
+    pub(crate) fn into_parts(self) -> (String, HashMap<String, Device>) {
+        (self.name, self.devices)
+    }
 }
 
 /// Trait for types that provide controlled access to devices.
@@ -146,18 +798,55 @@ impl AccessDevice for SmartRoom {
     /// * `Ok(&Device)` - A reference to the device if found
     /// * `Err(AccessError)` - An error with a descriptive message if the device was not found
     fn access_device(&self, key: &str) -> Result<&Device, AccessError> {
-        self.view_device(key).ok_or(AccessError {
-            message: format!(
-                "Device with the name '{}' not found in the room '{}'",
-                key, self.name
-            ),
-        })
+        self.view_device(key)
+            .ok_or_else(|| AccessError::device_not_found(key, &self.name))
     }
 }
 
-#[macro_export]
-macro_rules! create_room {
-    ($name:expr, $( $key:expr => $value:expr ),* $(,)? ) => {{
+/// Trait for types that provide controlled mutable access to devices.
+///
+/// This is the mutable counterpart of `AccessDevice`, reporting a missing device
+/// with the same `AccessError` instead of a bare `Option`.
+pub trait AccessDeviceMut {
+    /// Attempts to access a device by its key for modification.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The unique identifier for the device to access.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// * `Ok(&mut Device)` - A mutable reference to the requested device if found
+    /// * `Err(AccessError)` - An error if the device could not be found
+    fn access_device_mut(&mut self, key: &str) -> Result<&mut Device, AccessError>;
+}
+
+impl AccessDeviceMut for SmartRoom {
+    /// Provides controlled mutable access to devices in the smart room.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the device in the room's device collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(&mut Device)` - A mutable reference to the device if found
+    /// * `Err(AccessError)` - An error with a descriptive message if the device was not found
+    fn access_device_mut(&mut self, key: &str) -> Result<&mut Device, AccessError> {
+        self.devices
+            .get_mut(key)
+            .ok_or_else(|| AccessError::device_not_found(key, &self.name))
+    }
+}
+
+/// Creates a `SmartRoom` from `key => device` pairs.
+///
+/// Duplicate keys are not reported: the last device with a given key wins.
+/// Use `try_create_room!` when duplicates should be treated as an error.
+#[macro_export]
+macro_rules! create_room {
+    ($name:expr, $( $key:expr => $value:expr ),* $(,)? ) => {{
         let devices = std::collections::HashMap::from(
           [
             $( ($key.to_string(), $value) ),*
@@ -167,10 +856,26 @@ macro_rules! create_room {
     }};
 }
 
+/// Creates a `SmartRoom` from `key => device` pairs, returning a `Result`.
+///
+/// Keys are arbitrary expressions, so duplicates can only be detected at runtime.
+/// Unlike `create_room!`, which silently keeps the last device for a repeated key,
+/// this macro returns a `DuplicateKeyError` naming the first repeated key.
+#[macro_export]
+macro_rules! try_create_room {
+    ($name:expr, $( $key:expr => $value:expr ),* $(,)? ) => {{
+        $crate::smart_room::SmartRoom::try_new(
+            $name.to_string(),
+            [
+              $( ($key.to_string(), $value) ),*
+            ],
+        )
+    }};
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::smart_devices::{Celsius, OutletDevice, OutletState, Watt};
 
     const TEST_DEFAULT_DEVICE: Device = Device::Empty;
 
@@ -227,7 +932,7 @@ mod tests {
 
         assert_eq!(room.devices.len(), 3);
         assert_eq!(room.view_device("Some device"), None);
-        assert_eq!(room.view_device("Lighter").is_some(), true);
+        assert!(room.view_device("Lighter").is_some());
         assert_eq!(
             room.view_device("Lighter")
                 .unwrap_or(&TEST_DEFAULT_DEVICE)
@@ -240,7 +945,7 @@ mod tests {
                 .info(),
             "Smart Outlet: Lighter - Current State: On, Power Usage: 100 Watt"
         );
-        assert_eq!(room.view_device("PC").is_some(), true);
+        assert!(room.view_device("PC").is_some());
         assert_eq!(
             room.view_device("PC")
                 .unwrap_or(&TEST_DEFAULT_DEVICE)
@@ -253,7 +958,7 @@ mod tests {
                 .info(),
             "Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"
         );
-        assert_eq!(room.view_device("Electronic thermometer").is_some(), true);
+        assert!(room.view_device("Electronic thermometer").is_some());
         assert_eq!(
             room.view_device("Electronic thermometer")
                 .unwrap_or(&TEST_DEFAULT_DEVICE)
@@ -541,7 +1246,656 @@ Smart Room: Living Room:
         let err = room.access_device("Non-existing device").unwrap_err();
         assert_eq!(
             err.to_string(),
-            "AccessError: Device with the name 'Non-existing device' not found in the room 'Living Room'"
+            "smart-home error: Device with the name 'Non-existing device' not found in the room 'Living Room'"
+        );
+    }
+
+    #[test]
+    fn smart_room_iter_devices_test() {
+        let room = create_room!(
+            "Living Room",
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+        );
+
+        let keys: Vec<&str> = room.iter_devices().map(|(key, _)| key).collect();
+        assert_eq!(keys, vec!["Electronic thermometer", "Lighter", "PC"]);
+        assert!(
+            room.iter_devices()
+                .all(|(key, device)| device.name() == key)
+        );
+    }
+
+    #[test]
+    fn smart_room_total_power_usage_test() {
+        let room = create_room!(
+            "Bedroom",
+            "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
+            "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
+            "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius)
+        );
+        assert_eq!(room.total_power_usage(), 250 as Watt);
+        assert_eq!(create_room!("Kitchen",).total_power_usage(), 0 as Watt);
+    }
+
+    #[test]
+    fn smart_room_swap_device_test() {
+        let original = Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt);
+        let mut room = create_room!(
+            "Living Room",
+            "PC" => original.clone()
+        );
+
+        let replacement = Device::new_outlet("PC".to_string(), OutletState::Off, 400 as Watt);
+        let old = room.swap_device("PC", replacement.clone());
+        assert_eq!(old, Some(original));
+        assert_eq!(room.view_device("PC"), Some(&replacement));
+        assert_eq!(room.devices.len(), 1);
+
+        let thermometer = Device::new_thermometer("Thermometer".to_string(), 22.5 as Celsius);
+        assert_eq!(room.swap_device("Thermometer", thermometer), None);
+        assert_eq!(room.devices.len(), 2);
+    }
+
+    #[test]
+    fn smart_room_try_macro_create_test() {
+        let room = try_create_room!(
+            "Living Room",
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+        )
+        .unwrap();
+        assert_eq!(room.name(), "Living Room");
+        assert_eq!(room.devices.len(), 2);
+
+        let room = try_create_room!("Kitchen",).unwrap();
+        assert_eq!(room.devices.len(), 0);
+    }
+
+    #[test]
+    fn smart_room_try_macro_duplicate_keys_test() {
+        let result = try_create_room!(
+            "Living Room",
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::Off, 300 as Watt),
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "smart-home error: Device key 'PC' is used more than once in the room 'Living Room'"
+        );
+
+        let room = create_room!(
+            "Living Room",
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::Off, 300 as Watt),
+        );
+        assert_eq!(room.devices.len(), 1);
+    }
+
+    #[test]
+    fn smart_room_enforce_power_cap_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Heater" => Device::new_outlet("Heater".to_string(), OutletState::On, 1000 as Watt),
+            "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+        );
+        assert_eq!(room.total_power_usage(), 1350 as Watt);
+
+        assert_eq!(room.enforce_power_cap(500), vec!["Heater".to_string()]);
+        assert_eq!(room.total_power_usage(), 350 as Watt);
+        assert_eq!(
+            room.view_device("Heater").unwrap().info(),
+            "Smart Outlet: Heater - Current State: Off, Power Usage: 0 Watt"
+        );
+
+        assert!(room.enforce_power_cap(350).is_empty());
+        assert_eq!(
+            room.enforce_power_cap(0),
+            vec!["PC".to_string(), "Lighter".to_string()]
+        );
+        assert_eq!(room.total_power_usage(), 0 as Watt);
+    }
+
+    #[test]
+    fn smart_room_set_name_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt)
+        );
+        room.set_name("Study".to_string());
+        assert_eq!(room.name(), "Study");
+        assert!(room.info().starts_with("\nSmart Room: Study:\n"));
+    }
+
+    #[test]
+    fn smart_room_rename_devices_with_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+        );
+
+        assert!(room.rename_devices_with(|key| key.to_uppercase()).is_ok());
+        assert_eq!(room.devices.len(), 3);
+        assert!(room.view_device("Lighter").is_none());
+        assert_eq!(room.view_device("LIGHTER").unwrap().name(), "LIGHTER");
+        assert_eq!(room.view_device("PC").unwrap().name(), "PC");
+        assert_eq!(
+            room.access_device("ELECTRONIC THERMOMETER").unwrap().info(),
+            "Thermometer: ELECTRONIC THERMOMETER - Current Temperature: 22.50°C"
+        );
+    }
+
+    #[test]
+    fn smart_room_rename_devices_with_collision_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "pc" => Device::new_outlet("pc".to_string(), OutletState::On, 100 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+        );
+
+        let err = room
+            .rename_devices_with(|key| key.to_lowercase())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "smart-home error: Devices 'PC' and 'pc' would both be renamed to 'pc' in the room 'Living Room'"
+        );
+        assert_eq!(room.view_device("PC").unwrap().name(), "PC");
+        assert_eq!(room.view_device("pc").unwrap().name(), "pc");
+    }
+
+    #[test]
+    fn smart_room_validate_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Placeholder" => Device::Empty,
+        );
+        assert_eq!(room.validate(), Ok(()));
+
+        room.add_device(
+            "Lamp".to_string(),
+            Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+        );
+        room.add_device(
+            "Thermometer".to_string(),
+            Device::new_thermometer(String::new(), 22.5 as Celsius),
+        );
+        assert_eq!(
+            room.validate(),
+            Err(vec![
+                "Device 'Lighter' in the room 'Living Room' is stored under the key 'Lamp'"
+                    .to_string(),
+                "Device 'Thermometer' in the room 'Living Room' has an empty name".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn smart_room_split_off_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius),
+            "Window thermometer" => Device::new_thermometer("Window thermometer".to_string(), 12.0 as Celsius)
+        );
+
+        let sensors = room.split_off("Sensors".to_string(), |_, device| {
+            matches!(device, Device::ThermometerType(_))
+        });
+
+        assert_eq!(sensors.name(), "Sensors");
+        let sensor_keys: Vec<&str> = sensors.iter_devices().map(|(key, _)| key).collect();
+        assert_eq!(
+            sensor_keys,
+            vec!["Electronic thermometer", "Window thermometer"]
+        );
+        let room_keys: Vec<&str> = room.iter_devices().map(|(key, _)| key).collect();
+        assert_eq!(room_keys, vec!["Lighter", "PC"]);
+    }
+
+    #[test]
+    fn smart_room_devices_total_power_test() {
+        let room = create_room!(
+            "Bedroom",
+            "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
+            "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
+            "Lamp" => Device::new_outlet("Lamp".to_string(), OutletState::On, 60 as Watt),
+            "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius)
+        );
+        assert_eq!(room.devices().count(), 4);
+        assert_eq!(room.devices().total_power(), 310 as Watt);
+        assert_eq!(room.devices().total_power(), room.total_power_usage());
+        assert_eq!(
+            room.devices()
+                .filter(|device| device.name().contains("Outlet"))
+                .total_power(),
+            250 as Watt
+        );
+    }
+
+    #[test]
+    fn smart_room_access_device_mut_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+        );
+
+        match room.access_device_mut("PC").unwrap() {
+            Device::OutletType(outlet) => outlet.switch(),
+            _ => panic!("Expected OutletType"),
+        }
+        assert_eq!(
+            room.view_device("PC").unwrap().info(),
+            "Smart Outlet: PC - Current State: Off, Power Usage: 0 Watt"
+        );
+
+        assert_eq!(
+            room.access_device_mut("TV").unwrap_err(),
+            AccessError::device_not_found("TV", "Living Room")
+        );
+    }
+
+    #[test]
+    fn smart_room_thermometer_readings_test() {
+        let room = create_room!(
+            "Living Room",
+            "Window thermometer" => Device::new_thermometer("Window thermometer".to_string(), 12.0 as Celsius),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+        );
+        assert_eq!(
+            room.thermometer_readings(),
+            vec![
+                ("Electronic thermometer".to_string(), 22.5 as Celsius),
+                ("Window thermometer".to_string(), 12.0 as Celsius),
+            ]
+        );
+    }
+
+    #[test]
+    fn smart_room_iter_devices_sorted_test() {
+        let room = create_room!(
+            "Living Room",
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+        );
+
+        let entries: Vec<(&str, &Device)> = room.iter_devices().collect();
+        let keys: Vec<&str> = entries.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, vec!["Electronic thermometer", "Lighter", "PC"]);
+        assert_eq!(entries[2].1.name(), "PC");
+    }
+
+    fn merge_rooms() -> (SmartRoom, SmartRoom) {
+        let room = create_room!(
+            "Living Room",
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt)
+        );
+        let other = create_room!(
+            "Study",
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::Off, 400 as Watt),
+            "Thermometer" => Device::new_thermometer("Thermometer".to_string(), 21.0 as Celsius)
+        );
+        (room, other)
+    }
+
+    #[test]
+    fn smart_room_merge_from_keep_existing_test() {
+        let (mut room, other) = merge_rooms();
+        room.merge_from(other, MergeStrategy::KeepExisting);
+
+        assert_eq!(room.name(), "Living Room");
+        assert_eq!(room.devices.len(), 3);
+        assert_eq!(
+            room.view_device("PC").unwrap().info(),
+            "Smart Outlet: PC - Current State: On, Power Usage: 250 Watt"
+        );
+        assert!(room.view_device("Thermometer").is_some());
+    }
+
+    #[test]
+    fn smart_room_merge_from_overwrite_test() {
+        let (mut room, other) = merge_rooms();
+        room.merge_from(other, MergeStrategy::Overwrite);
+
+        assert_eq!(room.name(), "Living Room");
+        assert_eq!(room.devices.len(), 3);
+        assert_eq!(
+            room.view_device("PC").unwrap().info(),
+            "Smart Outlet: PC - Current State: Off, Power Usage: 0 Watt"
+        );
+        assert!(room.view_device("Lighter").is_some());
+    }
+
+    #[test]
+    fn smart_room_write_info_test() {
+        let room = create_room!(
+            "Living Room",
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt)
+        );
+        let mut buffer = String::new();
+        room.write_info(&mut buffer).unwrap();
+        assert_eq!(buffer, room.info());
+
+        let empty = create_room!("Attic",);
+        let mut buffer = String::new();
+        empty.write_info(&mut buffer).unwrap();
+        assert_eq!(buffer, "\nSmart Room: Attic:\n Total devices: 0\n  ");
+    }
+
+    #[test]
+    fn smart_room_power_map_test() {
+        let room = create_room!(
+            "Living Room",
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::Off, 100 as Watt),
+            "Thermometer" => Device::new_thermometer("Thermometer".to_string(), 22.5 as Celsius)
+        );
+        let power_map = room.power_map();
+        assert_eq!(
+            power_map.into_iter().collect::<Vec<_>>(),
+            vec![("Lighter".to_string(), 0), ("PC".to_string(), 250)]
+        );
+    }
+
+    #[test]
+    fn smart_room_total_power_rating_test() {
+        let room = create_room!(
+            "Bedroom",
+            "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
+            "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
+            "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius)
+        );
+        assert_eq!(room.total_power_rating(), 400);
+        assert_eq!(room.total_power_usage(), 250);
+    }
+
+    #[test]
+    fn smart_room_shrink_to_fit_test() {
+        let mut room = SmartRoom::new("Storage".to_string(), HashMap::new());
+        for i in 0..64 {
+            let key = format!("Outlet {}", i);
+            room.add_device(key.clone(), Device::new_outlet(key, OutletState::Off, 10));
+        }
+        let full_capacity = room.capacity();
+        assert!(full_capacity >= 64);
+
+        for i in 0..64 {
+            room.remove_device(&format!("Outlet {}", i));
+        }
+        assert!(room.is_empty());
+
+        room.shrink_to_fit();
+        assert!(room.capacity() < full_capacity);
+    }
+
+    #[test]
+    fn smart_room_partition_by_kind_test() {
+        let room = create_room!(
+            "Bedroom",
+            "Attached Outlet" => Device::new_outlet("Attached Outlet".to_string(), OutletState::On, 250 as Watt),
+            "Light Outlet" => Device::new_outlet("Light Outlet".to_string(), OutletState::Off, 150 as Watt),
+            "Electron thermometer" => Device::new_thermometer("Electron thermometer".to_string(), 22.5 as Celsius),
+            "Spare" => Device::Empty
+        );
+        let (outlets, thermometers) = room.partition_by_kind();
+        let outlet_names: Vec<String> = outlets.iter().map(|outlet| outlet.name()).collect();
+        assert_eq!(outlet_names, vec!["Attached Outlet", "Light Outlet"]);
+        assert_eq!(thermometers.len(), 1);
+        assert_eq!(thermometers[0].name(), "Electron thermometer");
+        assert_eq!(thermometers[0].current_temperature(), 22.5);
+    }
+
+    #[test]
+    fn smart_room_info_lines_test() {
+        let room = create_room!(
+            "Living Room",
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::Off, 250 as Watt),
+            "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+        );
+        let lines = room.info_lines();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines,
+            vec![
+                "[0]: Thermometer: Electronic thermometer - Current Temperature: 22.50°C",
+                "[1]: Smart Outlet: Lighter - Current State: On, Power Usage: 100 Watt",
+                "[2]: Smart Outlet: PC - Current State: Off, Power Usage: 0 Watt",
+            ]
+        );
+        for line in &lines {
+            assert!(room.info().contains(line.as_str()));
+        }
+    }
+
+    #[test]
+    fn smart_room_busiest_outlet_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Heater" => Device::new_outlet("Heater".to_string(), OutletState::Off, 2000 as Watt),
+            "Thermometer" => Device::new_thermometer("Thermometer".to_string(), 22.5 as Celsius)
+        );
+        assert_eq!(room.busiest_outlet(), Some(("PC", 250)));
+
+        room.add_device(
+            "Console".to_string(),
+            Device::new_outlet("Console".to_string(), OutletState::On, 250 as Watt),
+        );
+        assert_eq!(room.busiest_outlet(), Some(("Console", 250)));
+
+        let idle = create_room!(
+            "Attic",
+            "Lamp" => Device::new_outlet("Lamp".to_string(), OutletState::Off, 40 as Watt)
+        );
+        assert_eq!(idle.busiest_outlet(), None);
+    }
+
+    #[test]
+    fn smart_room_upsert_device_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt)
+        );
+
+        let replaced = room.upsert_device(
+            "PC".to_string(),
+            Device::new_outlet("Workstation".to_string(), OutletState::On, 400 as Watt),
+        );
+        assert_eq!(
+            replaced,
+            Some(Device::new_outlet(
+                "PC".to_string(),
+                OutletState::On,
+                250 as Watt
+            ))
+        );
+        assert_eq!(room.view_device("PC").unwrap().name(), "PC");
+        assert_eq!(room.total_power_usage(), 400);
+
+        let replaced = room.upsert_device(
+            "Lamp".to_string(),
+            Device::new_outlet("Unnamed".to_string(), OutletState::Off, 40 as Watt),
+        );
+        assert_eq!(replaced, None);
+        assert_eq!(room.view_device("Lamp").unwrap().name(), "Lamp");
+        assert!(room.validate().is_ok());
+    }
+
+    #[test]
+    fn smart_room_get_many_mut_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::Off, 250 as Watt)
+        );
+        let [lighter, pc] = room.get_many_mut(["Lighter", "PC"]).unwrap();
+        lighter.try_as_outlet_mut().unwrap().turn_off();
+        pc.try_as_outlet_mut().unwrap().turn_on();
+        assert_eq!(room.total_power_usage(), 250);
+
+        assert!(room.get_many_mut(["PC", "PC"]).is_none());
+        assert!(room.get_many_mut(["PC", "Heater"]).is_none());
+        assert!(room.get_many_mut([]).is_some());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn smart_room_to_json_roundtrip_test() {
+        let room = create_room!(
+            "Living Room",
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::Off, 250 as Watt),
+            "Thermometer" => Device::new_thermometer("Thermometer".to_string(), 22.5 as Celsius),
+            "Spare" => Device::Empty
+        );
+        let json = room.to_json().unwrap();
+        let decoded: SmartRoom = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, room);
+
+        let manual: serde_json::Value = serde_json::from_str(&room.to_json_manual()).unwrap();
+        let serde: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde, manual);
+    }
+
+    #[test]
+    fn smart_room_toggle_device_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::Off, 250 as Watt),
+            "Thermometer" => Device::new_thermometer("Thermometer".to_string(), 22.5 as Celsius)
+        );
+        assert_eq!(room.toggle_device("PC"), Ok(OutletState::On));
+        assert_eq!(room.total_power_usage(), 250);
+        assert_eq!(room.toggle_device("PC"), Ok(OutletState::Off));
+
+        let error = room.toggle_device("Thermometer").unwrap_err();
+        assert_eq!(error.kind, AccessErrorKind::NotAnOutlet);
+        assert_eq!(
+            error.to_string(),
+            "smart-home error: Device with the name 'Thermometer' in the room 'Living Room' is not an outlet"
+        );
+        let error = room.toggle_device("Heater").unwrap_err();
+        assert_eq!(error.kind, AccessErrorKind::NotFound);
+        assert_eq!(
+            error,
+            AccessError::device_not_found("Heater", "Living Room")
+        );
+
+        room.add_device("Spare".to_string(), Device::Empty);
+        assert_eq!(
+            room.toggle_device("Spare"),
+            Err(AccessError::not_an_outlet("Spare", "Living Room"))
+        );
+    }
+
+    #[test]
+    fn smart_room_view_device_ci_test() {
+        let room = SmartRoom::new(
+            "Bedroom".to_string(),
+            HashMap::from([
+                (
+                    "PC".to_string(),
+                    Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+                ),
+                (
+                    "pc".to_string(),
+                    Device::new_outlet("pc".to_string(), OutletState::Off, 100 as Watt),
+                ),
+                (
+                    "Thermometer".to_string(),
+                    Device::new_thermometer("Thermometer".to_string(), 22.0),
+                ),
+            ]),
+        );
+
+        assert_eq!(
+            room.view_device_ci("THERMOMETER").unwrap().name(),
+            "Thermometer"
+        );
+        assert_eq!(room.view_device_ci("Pc").unwrap().name(), "PC");
+        assert!(room.view_device_ci("lamp").is_none());
+
+        assert_eq!(room.view_device("pc").unwrap().name(), "pc");
+        assert!(room.view_device("thermometer").is_none());
+    }
+
+    #[test]
+    fn smart_room_outlets_in_range_test() {
+        let room = create_room!(
+            "Living Room",
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "Fan" => Device::new_outlet("Fan".to_string(), OutletState::Off, 150 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+        );
+        let names = |outlets: Vec<&Outlet>| -> Vec<String> {
+            outlets.iter().map(|outlet| outlet.name()).collect()
+        };
+        assert_eq!(
+            names(room.outlets_in_range(100, 150)),
+            vec!["Fan", "Lighter"]
+        );
+        assert_eq!(names(room.outlets_in_range(151, 250)), vec!["PC"]);
+        assert!(room.outlets_in_range(300, 500).is_empty());
+        assert!(room.outlets_in_range(250, 100).is_empty());
+    }
+
+    #[test]
+    fn smart_room_extract_devices_test() {
+        let mut room = create_room!(
+            "Living Room",
+            "Lighter" => Device::new_outlet("Lighter".to_string(), OutletState::On, 100 as Watt),
+            "Fan" => Device::new_outlet("Fan".to_string(), OutletState::Off, 150 as Watt),
+            "PC" => Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            "Electronic thermometer" => Device::new_thermometer("Electronic thermometer".to_string(), 22.5 as Celsius)
+        );
+        let extracted = room.extract_devices(|_, device| {
+            matches!(device, Device::OutletType(outlet) if outlet.state() == OutletState::On)
+        });
+        let keys: Vec<&str> = extracted.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, vec!["Lighter", "PC"]);
+        assert_eq!(extracted[1].1.power_usage(), 250);
+
+        assert!(room.view_device("Lighter").is_none());
+        assert!(room.view_device("PC").is_none());
+        assert!(room.view_device("Fan").is_some());
+        assert!(room.view_device("Electronic thermometer").is_some());
+        assert_eq!(room.total_power_usage(), 0);
+
+        assert!(room.extract_devices(|_, _| false).is_empty());
+    }
+
+    #[test]
+    fn smart_room_add_device_normalized_test() {
+        let mut room = SmartRoom::new("Living Room".to_string(), HashMap::new());
+        room.add_device_normalized(
+            " PC ",
+            Device::new_outlet(" PC ".to_string(), OutletState::On, 250 as Watt),
+        );
+        room.add_device_normalized("\tSpare\n", Device::Empty);
+
+        assert_eq!(room.view_device("PC").unwrap().name(), "PC");
+        assert!(room.view_device(" PC ").is_none());
+        assert!(room.view_device("Spare").is_some());
+
+        room.add_device(
+            " Lamp ".to_string(),
+            Device::new_outlet(" Lamp ".to_string(), OutletState::Off, 60 as Watt),
         );
+        assert_eq!(room.view_device(" Lamp ").unwrap().name(), " Lamp ");
     }
 }
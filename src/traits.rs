@@ -1,4 +1,81 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::Any;
+
 pub trait Information {
     fn name(&self) -> String;
     fn info(&self) -> String;
 }
+
+/// A device that can be stored as a trait object and downcast back to its concrete type.
+pub trait SmartDevice: Information {
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// Renders items as a two-column `Name | Info` table, one row per item in the given order.
+///
+/// The name column is padded to the longest name. Multi-line reports, such as those of
+/// rooms and homes, continue on the following lines with an empty name cell.
+pub fn render_table<'a>(items: impl IntoIterator<Item = &'a dyn Information>) -> String {
+    let rows: Vec<(String, String)> = items
+        .into_iter()
+        .map(|item| (item.name(), item.info()))
+        .collect();
+    let name_width = rows
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .chain(Some("Name".len()))
+        .max()
+        .unwrap_or(0);
+    let info_width = rows
+        .iter()
+        .flat_map(|(_, info)| info.lines())
+        .map(|line| line.chars().count())
+        .chain(Some("Info".len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = Vec::new();
+    lines.push(format!("{:<name_width$} | Info", "Name"));
+    lines.push(format!("{:-<name_width$}-+-{:-<info_width$}", "", ""));
+    for (name, info) in &rows {
+        let mut info_lines = info.lines();
+        lines.push(format!(
+            "{:<name_width$} | {}",
+            name,
+            info_lines.next().unwrap_or("")
+        ));
+        for line in info_lines {
+            lines.push(format!("{:<name_width$} | {}", "", line));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::smart_devices::{Device, OutletState, Watt};
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn render_table_test() {
+        let devices = [
+            Device::new_outlet("PC".to_string(), OutletState::On, 250 as Watt),
+            Device::new_thermometer("Bedroom".to_string(), 22.5),
+        ];
+        let table = render_table(devices.iter().map(|device| device as &dyn Information));
+        assert_eq!(
+            table.lines().collect::<Vec<&str>>(),
+            vec![
+                "Name    | Info",
+                "--------+------------------------------------------------------------",
+                "PC      | Smart Outlet: PC - Current State: On, Power Usage: 250 Watt",
+                "Bedroom | Thermometer: Bedroom - Current Temperature: 22.50°C",
+            ]
+        );
+        assert_eq!(render_table([]), "Name | Info\n-----+-----");
+    }
+}